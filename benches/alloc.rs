@@ -0,0 +1,258 @@
+//! Heap usage of each library's block and state decode and encode, measured
+//! with dhat. Every operation runs once under a fresh profiler, with its input
+//! prepared beforehand, so the numbers only cover the operation itself.
+
+#[cfg(any(feature = "block", feature = "state"))]
+use dhat::HeapStats;
+
+#[cfg(all(feature = "sszb", any(feature = "block", feature = "state")))]
+use sszb::SszEncode;
+
+#[cfg(any(feature = "block", feature = "state"))]
+use ssz_arena::{with_arena_spec, ArenaSpec, Network};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(any(feature = "block", feature = "state"))]
+struct Row {
+    library: &'static str,
+    operation: &'static str,
+    stats: HeapStats,
+}
+
+/// Runs `op` under its own profiler. The output stays alive until the stats
+/// are read, so `curr_bytes` is what the result holds on to.
+#[cfg(any(feature = "block", feature = "state"))]
+fn measure<T>(op: impl FnOnce() -> T) -> HeapStats {
+    // testing mode keeps dhat from writing a dhat-heap.json per operation
+    let _profiler = dhat::Profiler::builder().testing().build();
+    let output = op();
+    let stats = HeapStats::get();
+    drop(output);
+    stats
+}
+
+#[cfg(any(feature = "block", feature = "state"))]
+fn print_table(title: &str, rows: &[Row]) {
+    println!("\n{title}");
+    println!(
+        "| {:<10} | {:<9} | {:>11} | {:>14} | {:>14} | {:>14} |",
+        "library", "operation", "allocations", "total bytes", "peak bytes", "retained bytes"
+    );
+    println!(
+        "|{:-<12}|{:-<11}|{:->13}|{:->16}|{:->16}|{:->16}|",
+        "", "", "", "", "", ""
+    );
+    for row in rows {
+        println!(
+            "| {:<10} | {:<9} | {:>11} | {:>14} | {:>14} | {:>14} |",
+            row.library,
+            row.operation,
+            row.stats.total_blocks,
+            row.stats.total_bytes,
+            row.stats.max_bytes,
+            row.stats.curr_bytes
+        );
+    }
+}
+
+#[cfg(any(feature = "block", feature = "state"))]
+fn load_or_skip(
+    object: &str,
+    load: fn() -> Result<Vec<u8>, ssz_arena::ArenaFetchError>,
+) -> Option<Vec<u8>> {
+    match load() {
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            eprintln!("skipping {object} allocations: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(feature = "block")]
+fn beacon_block<S: ArenaSpec>() {
+    use ssz_arena::{load_block_bytes, peek_slot, FixtureKind};
+
+    let Some(block_bytes) = load_or_skip("SignedBeaconBlock", load_block_bytes) else {
+        return;
+    };
+    let Some(slot) = peek_slot(FixtureKind::Block, &block_bytes) else {
+        eprintln!("skipping SignedBeaconBlock allocations: fixture too short to contain a slot");
+        return;
+    };
+    let fork = S::NETWORK.fork_at_slot(slot);
+    let mut rows = Vec::new();
+
+    #[cfg(feature = "sszb")]
+    {
+        type SignedBeaconBlock<S> = ssz_arena::SignedBeaconBlockAnyFork<<S as ArenaSpec>::Preset>;
+
+        rows.push(Row {
+            library: "Sszb",
+            operation: "decode",
+            stats: measure(|| {
+                SignedBeaconBlock::<S>::from_ssz_bytes_for_fork(&block_bytes, fork).unwrap()
+            }),
+        });
+        let block = SignedBeaconBlock::<S>::from_ssz_bytes_for_fork(&block_bytes, fork).unwrap();
+        rows.push(Row {
+            library: "Sszb",
+            operation: "encode",
+            stats: measure(|| block.to_ssz()),
+        });
+    }
+
+    #[cfg(feature = "sigp")]
+    {
+        use sigp_types::{
+            ssz_tagged_signed_beacon_block::encode::as_ssz_bytes as sigp_block_encode,
+            SignedBeaconBlock as SigpBeaconBlock,
+        };
+
+        let decode =
+            || SigpBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(&block_bytes, fork.into());
+        rows.push(Row {
+            library: "Lighthouse",
+            operation: "decode",
+            stats: measure(|| decode().unwrap()),
+        });
+        let block = decode().unwrap();
+        rows.push(Row {
+            library: "Lighthouse",
+            operation: "encode",
+            stats: measure(|| sigp_block_encode(&block)),
+        });
+    }
+
+    #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        use grandine_ssz::{SszRead, SszWrite};
+        use grandine_types::combined::SignedBeaconBlock as GrandineBeaconBlock;
+
+        let config = S::grandine_config();
+        let decode =
+            || GrandineBeaconBlock::<S::Grandine>::from_ssz_unchecked(&config, &block_bytes);
+        rows.push(Row {
+            library: "Grandine",
+            operation: "decode",
+            stats: measure(|| decode().unwrap()),
+        });
+        let block = decode().unwrap();
+        rows.push(Row {
+            library: "Grandine",
+            operation: "encode",
+            stats: measure(|| block.to_ssz().unwrap()),
+        });
+    }
+
+    print_table(
+        &format!(
+            "SignedBeaconBlock ({}, {fork}, {} bytes)",
+            S::NETWORK,
+            block_bytes.len()
+        ),
+        &rows,
+    );
+}
+
+#[cfg(feature = "state")]
+fn beacon_state<S: ArenaSpec>() {
+    use ssz_arena::{load_state_bytes, peek_slot, FixtureKind};
+
+    let Some(state_bytes) = load_or_skip("BeaconState", load_state_bytes) else {
+        return;
+    };
+    let Some(slot) = peek_slot(FixtureKind::State, &state_bytes) else {
+        eprintln!("skipping BeaconState allocations: fixture too short to contain a slot");
+        return;
+    };
+    let fork = S::NETWORK.fork_at_slot(slot);
+    let mut rows = Vec::new();
+
+    #[cfg(feature = "sszb")]
+    {
+        type BeaconState<S> = ssz_arena::BeaconStateAnyFork<<S as ArenaSpec>::Preset>;
+
+        rows.push(Row {
+            library: "Sszb",
+            operation: "decode",
+            stats: measure(|| {
+                BeaconState::<S>::from_ssz_bytes_for_fork(&state_bytes, fork).unwrap()
+            }),
+        });
+        let state = BeaconState::<S>::from_ssz_bytes_for_fork(&state_bytes, fork).unwrap();
+        rows.push(Row {
+            library: "Sszb",
+            operation: "encode",
+            stats: measure(|| state.to_ssz()),
+        });
+    }
+
+    #[cfg(feature = "sigp")]
+    {
+        use sigp_types::BeaconState as SigpBeaconState;
+        use ssz::Encode;
+
+        let spec = S::sigp_spec();
+        let decode = || SigpBeaconState::<S::Sigp>::from_ssz_bytes(&state_bytes, &spec);
+        rows.push(Row {
+            library: "Lighthouse",
+            operation: "decode",
+            stats: measure(|| decode().unwrap()),
+        });
+        let state = decode().unwrap();
+        rows.push(Row {
+            library: "Lighthouse",
+            operation: "encode",
+            stats: measure(|| state.as_ssz_bytes()),
+        });
+    }
+
+    #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        use grandine_ssz::{SszRead, SszWrite};
+        use grandine_types::combined::BeaconState as GrandineBeaconState;
+
+        let config = S::grandine_config();
+        let decode =
+            || GrandineBeaconState::<S::Grandine>::from_ssz_unchecked(&config, &state_bytes);
+        rows.push(Row {
+            library: "Grandine",
+            operation: "decode",
+            stats: measure(|| decode().unwrap()),
+        });
+        let state = decode().unwrap();
+        rows.push(Row {
+            library: "Grandine",
+            operation: "encode",
+            stats: measure(|| state.to_ssz().unwrap()),
+        });
+    }
+
+    print_table(
+        &format!(
+            "BeaconState ({}, {fork}, {} bytes)",
+            S::NETWORK,
+            state_bytes.len()
+        ),
+        &rows,
+    );
+}
+
+fn main() {
+    #[cfg(not(any(feature = "block", feature = "state")))]
+    eprintln!("nothing to measure, enable the block and/or state features");
+
+    #[cfg(any(feature = "block", feature = "state"))]
+    match Network::from_env() {
+        Ok(network) => with_arena_spec!(network, S => {
+            #[cfg(feature = "block")]
+            beacon_block::<S>();
+            #[cfg(feature = "state")]
+            beacon_state::<S>();
+        }),
+        Err(e) => eprintln!("skipping allocation benchmarks: {e}"),
+    }
+}
@@ -2,6 +2,11 @@ use divan::{AllocProfiler, Divan};
 
 #[global_allocator]
 static ALLOC: AllocProfiler = AllocProfiler::system();
+
+/// Divan benches can't be instantiated per network like the criterion ones,
+/// so every decoder and fixture is mainnet's whatever `SSZ_ARENA_NETWORK` says.
+type S = ssz_arena::MainnetSpec;
+
 fn main() {
     // Run registered benchmarks.
     Divan::default().color(true).main();
@@ -11,9 +16,13 @@ fn main() {
 mod sszb {
     use divan::Bencher;
     use milhouse::List;
-    use ssz_arena::{get_block_bytes, get_state_bytes, BeaconState, SignedBeaconBlock};
+    use ssz_arena::{load_block_bytes_for, load_state_bytes_for, ArenaSpec};
     use sszb::{SszDecode, SszEncode};
 
+    type S = super::S;
+    type SignedBeaconBlock = ssz_arena::SignedBeaconBlockAnyFork<<S as ArenaSpec>::Preset>;
+    type BeaconState = ssz_arena::BeaconStateAnyFork<<S as ArenaSpec>::Preset>;
+
     type C = typenum::U1099511627776;
     const N: u64 = 1_000_000;
 
@@ -66,12 +75,11 @@ mod sszb {
     #[cfg(feature = "block")]
     #[divan::bench]
     fn encode_beacon_block_fast(bencher: Bencher) {
-        let bytes =
-            std::fs::read("beacon-block.ssz").unwrap_or_else(|_| get_block_bytes().unwrap());
+        let bytes = load_block_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
         bencher
             .with_inputs(|| {
                 let beacon_block =
-                    <SignedBeaconBlock as SszDecode>::from_ssz_bytes(bytes.as_slice()).unwrap();
+                    SignedBeaconBlock::from_ssz_bytes_for_network(&bytes, S::NETWORK).unwrap();
                 let len = SszEncode::ssz_bytes_len(&beacon_block);
                 let buf: Vec<u8> = vec![0u8; len];
                 (beacon_block, buf)
@@ -82,10 +90,9 @@ mod sszb {
     #[cfg(feature = "block")]
     #[divan::bench]
     fn decode_beacon_block(bencher: Bencher) {
-        let bytes =
-            std::fs::read("beacon-block.ssz").unwrap_or_else(|_| get_block_bytes().unwrap());
+        let bytes = load_block_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
         bencher.bench_local(move || {
-            <SignedBeaconBlock as SszDecode>::from_ssz_bytes(bytes.as_slice()).unwrap()
+            SignedBeaconBlock::from_ssz_bytes_for_network(&bytes, S::NETWORK).unwrap()
         });
     }
 
@@ -105,12 +112,11 @@ mod sszb {
     #[cfg(feature = "state")]
     #[divan::bench]
     fn encode_beacon_state_fast(bencher: Bencher) {
-        let bytes =
-            std::fs::read("beacon-state.ssz").unwrap_or_else(|_| get_state_bytes().unwrap());
+        let bytes = load_state_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
         bencher
             .with_inputs(|| {
                 let beacon_state =
-                    <BeaconState as SszDecode>::from_ssz_bytes(bytes.as_slice()).unwrap();
+                    BeaconState::from_ssz_bytes_for_network(&bytes, S::NETWORK).unwrap();
                 let len = SszEncode::ssz_bytes_len(&beacon_state);
                 let buf: Vec<u8> = vec![0u8; len];
                 (beacon_state, buf)
@@ -121,10 +127,9 @@ mod sszb {
     #[cfg(feature = "state")]
     #[divan::bench]
     fn decode_beacon_state(bencher: Bencher) {
-        let bytes =
-            std::fs::read("beacon-state.ssz").unwrap_or_else(|_| get_state_bytes().unwrap());
+        let bytes = load_state_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
         bencher.bench_local(move || {
-            <BeaconState as SszDecode>::from_ssz_bytes(bytes.as_slice()).unwrap()
+            BeaconState::from_ssz_bytes_for_network(&bytes, S::NETWORK).unwrap()
         });
     }
 }
@@ -188,11 +193,20 @@ mod sigp {
     use milhouse::List;
     use sigp_types::{
         ssz_tagged_signed_beacon_block::encode::as_ssz_bytes as sigp_block_encode,
-        BeaconState as SigpBeaconState, ChainSpec, ForkName, MainnetEthSpec,
-        SignedBeaconBlock as SigpBeaconBlock,
+        BeaconState as SigpBeaconState, ForkName, SignedBeaconBlock as SigpBeaconBlock,
     };
     use ssz::{Decode, Encode};
-    use ssz_arena::{get_block_bytes, get_state_bytes};
+    use ssz_arena::{
+        load_block_bytes_for, load_state_bytes_for, peek_slot, ArenaSpec, FixtureKind,
+    };
+
+    type S = super::S;
+
+    #[cfg(feature = "block")]
+    fn block_fork(bytes: &[u8]) -> ForkName {
+        let slot = peek_slot(FixtureKind::Block, bytes).expect("block contains a slot");
+        S::NETWORK.fork_at_slot(slot).into()
+    }
 
     type C = typenum::U1099511627776;
     const N: u64 = 1_000_000;
@@ -220,12 +234,12 @@ mod sigp {
     #[cfg(feature = "block")]
     #[divan::bench]
     fn decode_sigp_beacon_block(bencher: Bencher) {
-        let bytes =
-            std::fs::read("beacon-block.ssz").unwrap_or_else(|_| get_block_bytes().unwrap());
+        let bytes = load_block_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
+        let fork = block_fork(&bytes);
         bencher.bench_local(move || {
-            SigpBeaconBlock::<MainnetEthSpec>::from_ssz_bytes_for_fork(
+            SigpBeaconBlock::<<S as ArenaSpec>::Sigp>::from_ssz_bytes_for_fork(
                 bytes.as_slice(),
-                ForkName::Deneb,
+                fork,
             )
         });
     }
@@ -233,13 +247,13 @@ mod sigp {
     #[cfg(feature = "block")]
     #[divan::bench]
     fn encode_sigp_beacon_block(bencher: Bencher) {
-        let bytes =
-            std::fs::read("beacon-block.ssz").unwrap_or_else(|_| get_block_bytes().unwrap());
+        let bytes = load_block_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
+        let fork = block_fork(&bytes);
         bencher
             .with_inputs(move || {
-                let block = SigpBeaconBlock::<MainnetEthSpec>::from_ssz_bytes_for_fork(
+                let block = SigpBeaconBlock::<<S as ArenaSpec>::Sigp>::from_ssz_bytes_for_fork(
                     bytes.as_slice(),
-                    ForkName::Deneb,
+                    fork,
                 )
                 .unwrap();
                 block
@@ -250,13 +264,10 @@ mod sigp {
     #[cfg(feature = "state")]
     #[divan::bench]
     fn decode_sigp_beacon_state(bencher: Bencher) {
-        let bytes =
-            std::fs::read("beacon-state.ssz").unwrap_or_else(|_| get_state_bytes().unwrap());
+        let bytes = load_state_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
+        let spec = S::sigp_spec();
         bencher.bench_local(move || {
-            SigpBeaconState::<MainnetEthSpec>::from_ssz_bytes(
-                bytes.as_slice(),
-                &ChainSpec::default(),
-            )
+            SigpBeaconState::<<S as ArenaSpec>::Sigp>::from_ssz_bytes(bytes.as_slice(), &spec)
         });
     }
 
@@ -280,14 +291,14 @@ mod sigp {
 #[cfg(feature = "grandine")]
 #[divan::bench_group]
 mod grandine {
-    use crate::checkpointz::{get_block_bytes, get_state_bytes};
     use divan::Bencher;
     use grandine_ssz::SszRead;
-    use grandine_types::{
-        combined::{BeaconState as GrandineBeaconState, SignedBeaconBlock as GrandineBeaconBlock},
-        config::Config,
-        preset::Mainnet,
+    use grandine_types::combined::{
+        BeaconState as GrandineBeaconState, SignedBeaconBlock as GrandineBeaconBlock,
     };
+    use ssz_arena::{load_block_bytes_for, load_state_bytes_for, ArenaSpec};
+
+    type S = super::S;
 
     impl<T: SszSize + milhouse::Value, N> SszSize for milhouse::List<T, N> {
         const SIZE: Size = Size::Variable { minimum_size: 0 };
@@ -309,17 +320,25 @@ mod grandine {
     #[cfg(feature = "block")]
     #[divan::bench]
     fn decode_grandine_beacon_block(bencher: Bencher) {
-        let bytes = get_block_bytes().unwrap();
+        let bytes = load_block_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
+        let config = S::grandine_config();
         bencher.bench_local(move || {
-            GrandineBeaconBlock::<Mainnet>::from_ssz_unchecked(&Config::mainnet(), bytes.as_slice())
+            GrandineBeaconBlock::<<S as ArenaSpec>::Grandine>::from_ssz_unchecked(
+                &config,
+                bytes.as_slice(),
+            )
         });
     }
     #[cfg(feature = "state")]
     #[divan::bench]
     fn decode_grandine_beacon_state(bencher: Bencher) {
-        let bytes = get_state_bytes().unwrap();
+        let bytes = load_state_bytes_for(S::NETWORK).unwrap_or_else(|e| panic!("{e}"));
+        let config = S::grandine_config();
         bencher.bench_local(move || {
-            GrandineBeaconState::<Mainnet>::from_ssz_unchecked(&Config::mainnet(), bytes.as_slice())
+            GrandineBeaconState::<<S as ArenaSpec>::Grandine>::from_ssz_unchecked(
+                &config,
+                bytes.as_slice(),
+            )
         });
     }
 }
@@ -8,23 +8,49 @@ use sszb::{SszDecode, SszEncode};
 
 #[cfg(all(feature = "sigp", feature = "block"))]
 use sigp_types::{
-    ssz_tagged_signed_beacon_block::encode::as_ssz_bytes as sigp_block_encode, ForkName,
-    MainnetEthSpec, SignedBeaconBlock as SigpBeaconBlock,
+    ssz_tagged_signed_beacon_block::encode::as_ssz_bytes as sigp_block_encode,
+    SignedBeaconBlock as SigpBeaconBlock,
 };
 #[cfg(feature = "sigp")]
 use ssz::{ssz_encode, Decode, Encode};
+use tree_hash::TreeHash;
 
 #[cfg(feature = "grandine")]
-use grandine_ssz::{PersistentList, SszRead, SszWrite};
+use grandine_ssz::{PersistentList, SszHash, SszRead, SszWrite};
 #[cfg(all(feature = "grandine", feature = "state"))]
 use grandine_types::combined::BeaconState as GrandineBeaconState;
 #[cfg(all(feature = "grandine", feature = "block"))]
 use grandine_types::combined::SignedBeaconBlock as GrandineBeaconBlock;
 #[cfg(feature = "grandine")]
-use grandine_types::{config::Config, preset::Mainnet};
-#[cfg(feature = "grandine")]
 use try_from_iter::TryFromIterator;
 
+#[cfg(any(feature = "block", feature = "state"))]
+use ssz_arena::{with_arena_spec, ArenaSpec, Network};
+
+/// Mainnet keeps the bare group name so existing criterion baselines still line up.
+#[cfg(any(feature = "block", feature = "state"))]
+fn group_name(name: &str, network: Network) -> String {
+    match network {
+        Network::Mainnet => name.to_string(),
+        network => format!("{name}/{network}"),
+    }
+}
+
+/// Fetch failures skip the group with a message instead of panicking mid-run.
+#[cfg(any(feature = "block", feature = "state"))]
+fn load_or_skip(
+    group: &str,
+    load: fn() -> Result<Vec<u8>, ssz_arena::ArenaFetchError>,
+) -> Option<Vec<u8>> {
+    match load() {
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            eprintln!("skipping {group} benchmarks: {e}");
+            None
+        }
+    }
+}
+
 fn basic_types(c: &mut Criterion) {
     use milhouse::List;
 
@@ -133,24 +159,245 @@ fn container_types(c: &mut Criterion) {
     group.finish();
 }
 
+/// Lists are hashed from a fresh build each iteration, since milhouse,
+/// ghilhouse and grandine's persistent lists all memoize node hashes.
+fn tree_hash_types(c: &mut Criterion) {
+    use ssz_arena::ExecutionPayloadHeader;
+
+    type C = typenum::U1099511627776;
+    const N: u64 = 1_000_000;
+
+    let mut group = c.benchmark_group("List tree_hash");
+    group.throughput(Throughput::Bytes(N * 8));
+
+    #[cfg(feature = "sszb")]
+    group.bench_function(BenchmarkId::new("Sszb", "hash_tree_root"), |b| {
+        b.iter_batched(
+            || ghilhouse::List::<u64, C>::try_from_iter(0..N).unwrap(),
+            |list| list.tree_hash_root(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    #[cfg(feature = "sigp")]
+    group.bench_function(BenchmarkId::new("Sigp", "hash_tree_root"), |b| {
+        b.iter_batched(
+            || milhouse::List::<u64, C>::try_from_iter(0..N).unwrap(),
+            |list| list.tree_hash_root(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    #[cfg(feature = "grandine")]
+    group.bench_function(BenchmarkId::new("Grandine", "hash_tree_root"), |b| {
+        b.iter_batched(
+            || PersistentList::<u64, C>::try_from_iter(0..N).unwrap(),
+            |list| list.hash_tree_root(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+
+    type H = typenum::U1000000;
+    let size: usize = 1000;
+
+    let mut group = c.benchmark_group("ExecutionPayloadHeader List tree_hash");
+    group.sample_size(10);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let headers = vec![ExecutionPayloadHeader::default(); size];
+    let list =
+        ghilhouse::List::<ExecutionPayloadHeader, H>::try_from_iter(headers.clone()).unwrap();
+    group.throughput(Throughput::Bytes(list.to_ssz().len() as u64));
+
+    #[cfg(feature = "sszb")]
+    group.bench_function(BenchmarkId::new("Sszb", "hash_tree_root"), |b| {
+        b.iter_batched(
+            || {
+                ghilhouse::List::<ExecutionPayloadHeader, H>::try_from_iter(headers.clone())
+                    .unwrap()
+            },
+            |list| list.tree_hash_root(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    #[cfg(feature = "sigp")]
+    group.bench_function(BenchmarkId::new("Sigp", "hash_tree_root"), |b| {
+        use sigp_types::{ExecutionPayloadHeaderDeneb, MainnetEthSpec};
+
+        let headers = vec![ExecutionPayloadHeaderDeneb::<MainnetEthSpec>::default(); size];
+        b.iter_batched(
+            || milhouse::List::<_, H>::try_from_iter(headers.clone()).unwrap(),
+            |list| list.tree_hash_root(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    #[cfg(feature = "grandine")]
+    group.bench_function(BenchmarkId::new("Grandine", "hash_tree_root"), |b| {
+        use grandine_types::{deneb::containers::ExecutionPayloadHeader, preset::Mainnet};
+
+        let headers = vec![ExecutionPayloadHeader::<Mainnet>::default(); size];
+        b.iter_batched(
+            || PersistentList::<_, H>::try_from_iter(headers.clone()).unwrap(),
+            |list| list.hash_tree_root(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+/// Mainnet-sized payload: a couple hundred transactions of mixed sizes.
+fn transactions(c: &mut Criterion) {
+    use ssz_arena::{Transaction, Transactions, TxOpaque};
+
+    let txs: Vec<Transaction> = (0..200usize)
+        .map(|i| vec![i as u8; 100 + (i * 7919) % 2000].into())
+        .collect();
+    let tx_opaque = TxOpaque::try_from_iter(txs.iter().map(|tx| &tx[..])).unwrap();
+    let list = Transactions::from(txs);
+    let list_bytes = list.to_ssz();
+    assert_eq!(tx_opaque.to_ssz(), list_bytes);
+    assert_eq!(tx_opaque.tree_hash_root(), list.tree_hash_root());
+
+    let mut group = c.benchmark_group("Transactions tree_hash");
+    group.throughput(Throughput::Bytes(list_bytes.len() as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaque", "hash_tree_root"),
+        &tx_opaque,
+        |b, txs| b.iter(|| txs.tree_hash_root()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("VariableList", "hash_tree_root"),
+        &list,
+        |b, txs| b.iter(|| txs.tree_hash_root()),
+    );
+
+    group.finish();
+
+    // conversions sit next to the decodes they'd replace or follow
+    let mut group = c.benchmark_group("Transactions conversion");
+    group.throughput(Throughput::Bytes(list_bytes.len() as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaque", "decode"),
+        list_bytes.as_slice(),
+        |b, bytes| b.iter(|| <TxOpaque as SszDecode>::from_ssz_bytes(bytes).unwrap()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("VariableList", "decode"),
+        list_bytes.as_slice(),
+        |b, bytes| b.iter(|| <Transactions as SszDecode>::from_ssz_bytes(bytes).unwrap()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaque", "into VariableList"),
+        &tx_opaque,
+        |b, txs| b.iter(|| Transactions::from(txs)),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("VariableList", "into TxOpaque"),
+        &list,
+        |b, txs| b.iter(|| TxOpaque::try_from(txs).unwrap()),
+    );
+
+    #[cfg(feature = "sigp")]
+    {
+        use sigp_types::{MainnetEthSpec, TransactionsOpaque};
+
+        type SigpTransactions = TransactionsOpaque<MainnetEthSpec>;
+
+        group.bench_with_input(
+            BenchmarkId::new("Lighthouse", "decode"),
+            list_bytes.as_slice(),
+            |b, bytes| b.iter(|| SigpTransactions::from_ssz_bytes(bytes).unwrap()),
+        );
+        let sigp_txs = SigpTransactions::try_from(&tx_opaque).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("Lighthouse", "into TxOpaque"),
+            &sigp_txs,
+            |b, txs| b.iter(|| TxOpaque::try_from(txs).unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("TxOpaque", "into Lighthouse"),
+            &tx_opaque,
+            |b, txs| b.iter(|| SigpTransactions::try_from(txs).unwrap()),
+        );
+    }
+
+    #[cfg(feature = "grandine")]
+    {
+        use grandine_ssz::{ByteList, ContiguousList, SszReadDefault};
+
+        type GrandineTransactions =
+            ContiguousList<ByteList<typenum::U1073741824>, typenum::U1048576>;
+
+        group.bench_with_input(
+            BenchmarkId::new("Grandine", "decode"),
+            list_bytes.as_slice(),
+            |b, bytes| b.iter(|| GrandineTransactions::from_ssz_default(bytes).unwrap()),
+        );
+        let grandine_txs = GrandineTransactions::try_from(&tx_opaque).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("Grandine", "into TxOpaque"),
+            &grandine_txs,
+            |b, txs| b.iter(|| TxOpaque::try_from(txs).unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("TxOpaque", "into Grandine"),
+            &tx_opaque,
+            |b, txs| b.iter(|| GrandineTransactions::try_from(txs).unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
 #[cfg(feature = "block")]
 fn beacon_block(c: &mut Criterion) {
-    use ssz_arena::{get_block_bytes, SignedBeaconBlock};
+    match Network::from_env() {
+        Ok(network) => with_arena_spec!(network, S => bench_beacon_block::<S>(c)),
+        Err(e) => eprintln!("skipping SignedBeaconBlock benchmarks: {e}"),
+    }
+}
+
+#[cfg(feature = "block")]
+fn bench_beacon_block<S: ArenaSpec>(c: &mut Criterion) {
+    use ssz_arena::{load_block_bytes, peek_slot, verify_block, FixtureKind};
+
+    type SignedBeaconBlock<S> = ssz_arena::SignedBeaconBlockAnyFork<<S as ArenaSpec>::Preset>;
 
-    let mut group = c.benchmark_group("SignedBeaconBlock");
-    let block_bytes: Vec<u8> =
-        std::fs::read("beacon-block.ssz").unwrap_or_else(|_| get_block_bytes().unwrap());
+    let Some(block_bytes) = load_or_skip("SignedBeaconBlock", load_block_bytes) else {
+        return;
+    };
+    let Some(slot) = peek_slot(FixtureKind::Block, &block_bytes) else {
+        eprintln!("skipping SignedBeaconBlock benchmarks: fixture too short to contain a slot");
+        return;
+    };
+    let fork = S::NETWORK.fork_at_slot(slot);
+    // a decoder that disagrees with the others must fail the run, not win it
+    match verify_block::<S>(&block_bytes) {
+        Ok(verified) => eprintln!(
+            "SignedBeaconBlock {fork} fixture round-trips through {}",
+            verified.libraries.join(", ")
+        ),
+        Err(e) => panic!("SignedBeaconBlock verification failed: {e}"),
+    }
+
+    let mut group = c.benchmark_group(group_name("SignedBeaconBlock", S::NETWORK));
     group.throughput(Throughput::Bytes(block_bytes.len() as u64));
 
     #[cfg(feature = "sszb")]
     group.bench_with_input(
         BenchmarkId::new("Sszb", "decode"),
         block_bytes.as_slice(),
-        |b, bytes| b.iter(|| <SignedBeaconBlock as SszDecode>::from_ssz_bytes(bytes).unwrap()),
+        |b, bytes| b.iter(|| SignedBeaconBlock::<S>::from_ssz_bytes_for_fork(bytes, fork).unwrap()),
     );
     #[cfg(feature = "sszb")]
-    let beacon_block =
-        <SignedBeaconBlock as SszDecode>::from_ssz_bytes(block_bytes.as_slice()).unwrap();
+    let beacon_block = SignedBeaconBlock::<S>::from_ssz_bytes_for_fork(&block_bytes, fork).unwrap();
     #[cfg(feature = "sszb")]
     group.bench_with_input(
         BenchmarkId::new("Sszb", "encode"),
@@ -158,8 +405,7 @@ fn beacon_block(c: &mut Criterion) {
         |b, block| b.iter(|| block.to_ssz()),
     );
     #[cfg(feature = "sszb")]
-    let beacon_block =
-        <SignedBeaconBlock as SszDecode>::from_ssz_bytes(block_bytes.as_slice()).unwrap();
+    let beacon_block = SignedBeaconBlock::<S>::from_ssz_bytes_for_fork(&block_bytes, fork).unwrap();
     #[cfg(feature = "sszb")]
     group.bench_with_input(
         BenchmarkId::new("Sszb", "encode to slice"),
@@ -177,18 +423,15 @@ fn beacon_block(c: &mut Criterion) {
         block_bytes.as_slice(),
         |b, bytes| {
             b.iter(|| {
-                SigpBeaconBlock::<MainnetEthSpec>::from_ssz_bytes_for_fork(bytes, ForkName::Deneb)
-                    .unwrap()
+                SigpBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(bytes, fork.into()).unwrap()
             })
         },
     );
 
     #[cfg(feature = "sigp")]
-    let beacon_block = SigpBeaconBlock::<MainnetEthSpec>::from_ssz_bytes_for_fork(
-        block_bytes.as_slice(),
-        ForkName::Deneb,
-    )
-    .unwrap();
+    let beacon_block =
+        SigpBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(block_bytes.as_slice(), fork.into())
+            .unwrap();
 
     #[cfg(feature = "sigp")]
     group.bench_with_input(
@@ -198,28 +441,153 @@ fn beacon_block(c: &mut Criterion) {
     );
 
     #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        let config = S::grandine_config();
+        group.bench_with_input(
+            BenchmarkId::new("Grandine", "decode"),
+            block_bytes.as_slice(),
+            |b, bytes| {
+                b.iter(|| {
+                    GrandineBeaconBlock::<S::Grandine>::from_ssz_unchecked(&config, bytes).unwrap()
+                })
+            },
+        );
+
+        let beacon_block =
+            GrandineBeaconBlock::<S::Grandine>::from_ssz_unchecked(&config, block_bytes.as_slice())
+                .unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("Grandine", "encode"),
+            &beacon_block,
+            |b, block| b.iter(|| block.to_ssz()),
+        );
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group(group_name("SignedBeaconBlock tree_hash", S::NETWORK));
+    group.throughput(Throughput::Bytes(block_bytes.len() as u64));
+
+    #[cfg(feature = "sszb")]
     group.bench_with_input(
-        BenchmarkId::new("Grandine", "decode"),
-        block_bytes.as_slice(),
+        BenchmarkId::new("Sszb", "hash_tree_root"),
+        &SignedBeaconBlock::<S>::from_ssz_bytes_for_fork(&block_bytes, fork).unwrap(),
+        |b, block| b.iter(|| block.tree_hash_root()),
+    );
+
+    #[cfg(feature = "sigp")]
+    group.bench_with_input(
+        BenchmarkId::new("Lighthouse", "hash_tree_root"),
+        &SigpBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(block_bytes.as_slice(), fork.into())
+            .unwrap(),
+        |b, block| b.iter(|| block.tree_hash_root()),
+    );
+
+    #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        let config = S::grandine_config();
+        group.bench_with_input(
+            BenchmarkId::new("Grandine", "hash_tree_root"),
+            &GrandineBeaconBlock::<S::Grandine>::from_ssz_unchecked(
+                &config,
+                block_bytes.as_slice(),
+            )
+            .unwrap(),
+            |b, block| b.iter(|| block.hash_tree_root()),
+        );
+    }
+
+    group.finish();
+
+    #[cfg(feature = "sszb")]
+    {
+        let block = SignedBeaconBlock::<S>::from_ssz_bytes_for_fork(&block_bytes, fork).unwrap();
+        match block.transactions() {
+            Some(txs) => bench_block_transactions(c, S::NETWORK, &txs.to_ssz(), &block_bytes),
+            None => eprintln!("skipping block transaction benchmarks: {fork} has no payload"),
+        }
+    }
+}
+
+/// Owned vs zero-copy transaction lists on the fixture block's payload, where
+/// the transactions make up most of the block.
+#[cfg(all(feature = "block", feature = "sszb"))]
+fn bench_block_transactions(
+    c: &mut Criterion,
+    network: Network,
+    tx_bytes: &[u8],
+    block_bytes: &[u8],
+) {
+    use ssz_arena::{TxOpaque, TxOpaqueBytes, TxOpaqueRef};
+
+    eprintln!(
+        "block transactions are {} of the block's {} bytes",
+        tx_bytes.len(),
+        block_bytes.len()
+    );
+
+    let mut group = c.benchmark_group(group_name("Block transactions", network));
+    group.throughput(Throughput::Bytes(tx_bytes.len() as u64));
+
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaque", "decode"),
+        tx_bytes,
+        |b, bytes| b.iter(|| <TxOpaque as SszDecode>::from_ssz_bytes(bytes).unwrap()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaqueRef", "decode"),
+        tx_bytes,
+        |b, bytes| b.iter(|| TxOpaqueRef::from_ssz_bytes(bytes).unwrap()),
+    );
+    let shared = bytes::Bytes::copy_from_slice(tx_bytes);
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaqueBytes", "decode"),
+        &shared,
+        |b, bytes| b.iter(|| TxOpaqueBytes::from_ssz_bytes(bytes.clone()).unwrap()),
+    );
+
+    // the views read offsets on access, so walking every transaction is where they pay
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaque", "decode and iterate"),
+        tx_bytes,
         |b, bytes| {
             b.iter(|| {
-                GrandineBeaconBlock::<Mainnet>::from_ssz_unchecked(&Config::mainnet(), bytes)
-                    .unwrap()
+                let txs = <TxOpaque as SszDecode>::from_ssz_bytes(bytes).unwrap();
+                txs.iter().map(|tx| black_box(tx).len()).sum::<usize>()
+            })
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaqueRef", "decode and iterate"),
+        tx_bytes,
+        |b, bytes| {
+            b.iter(|| {
+                let txs = TxOpaqueRef::from_ssz_bytes(bytes).unwrap();
+                txs.iter().map(|tx| black_box(tx).len()).sum::<usize>()
             })
         },
     );
 
-    #[cfg(feature = "grandine")]
-    let beacon_block = GrandineBeaconBlock::<Mainnet>::from_ssz_unchecked(
-        &Config::mainnet(),
-        block_bytes.as_slice(),
-    )
-    .unwrap();
-    #[cfg(feature = "grandine")]
+    let owned = <TxOpaque as SszDecode>::from_ssz_bytes(tx_bytes).unwrap();
+    let borrowed = TxOpaqueRef::from_ssz_bytes(tx_bytes).unwrap();
+    assert_eq!(borrowed.tree_hash_root(), owned.tree_hash_root());
+    group.bench_with_input(BenchmarkId::new("TxOpaque", "encode"), &owned, |b, txs| {
+        b.iter(|| txs.to_ssz())
+    });
     group.bench_with_input(
-        BenchmarkId::new("Grandine", "encode"),
-        &beacon_block,
-        |b, bytes| b.iter(|| beacon_block.to_ssz()),
+        BenchmarkId::new("TxOpaqueRef", "encode"),
+        &borrowed,
+        |b, txs| b.iter(|| txs.to_ssz()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaque", "hash_tree_root"),
+        &owned,
+        |b, txs| b.iter(|| txs.tree_hash_root()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("TxOpaqueRef", "hash_tree_root"),
+        &borrowed,
+        |b, txs| b.iter(|| txs.tree_hash_root()),
     );
 
     group.finish();
@@ -227,15 +595,39 @@ fn beacon_block(c: &mut Criterion) {
 
 #[cfg(feature = "state")]
 fn beacon_state(c: &mut Criterion) {
-    use ssz_arena::{get_state_bytes, BeaconState};
+    match Network::from_env() {
+        Ok(network) => with_arena_spec!(network, S => bench_beacon_state::<S>(c)),
+        Err(e) => eprintln!("skipping BeaconState benchmarks: {e}"),
+    }
+}
+
+#[cfg(feature = "state")]
+fn bench_beacon_state<S: ArenaSpec>(c: &mut Criterion) {
+    use ssz_arena::{load_state_bytes, peek_slot, verify_state, FixtureKind};
 
     #[cfg(feature = "sigp")]
-    use sigp_types::{BeaconState as SigpBeaconState, ChainSpec, MainnetEthSpec};
+    use sigp_types::BeaconState as SigpBeaconState;
+
+    type BeaconState<S> = ssz_arena::BeaconStateAnyFork<<S as ArenaSpec>::Preset>;
+
+    let Some(state_bytes) = load_or_skip("BeaconState", load_state_bytes) else {
+        return;
+    };
+    let Some(slot) = peek_slot(FixtureKind::State, &state_bytes) else {
+        eprintln!("skipping BeaconState benchmarks: fixture too short to contain a slot");
+        return;
+    };
+    let fork = S::NETWORK.fork_at_slot(slot);
+    match verify_state::<S>(&state_bytes) {
+        Ok(verified) => eprintln!(
+            "BeaconState {fork} fixture round-trips through {}",
+            verified.libraries.join(", ")
+        ),
+        Err(e) => panic!("BeaconState verification failed: {e}"),
+    }
+    let beacon_state = BeaconState::<S>::from_ssz_bytes_for_fork(&state_bytes, fork).unwrap();
 
-    let mut group = c.benchmark_group("BeaconState");
-    let state_bytes: Vec<u8> =
-        std::fs::read("beacon-state.ssz").unwrap_or_else(|_| get_state_bytes().unwrap());
-    let beacon_state = <BeaconState as SszDecode>::from_ssz_bytes(state_bytes.as_slice()).unwrap();
+    let mut group = c.benchmark_group(group_name("BeaconState", S::NETWORK));
     group.throughput(Throughput::Bytes(state_bytes.len() as u64));
     group.sample_size(10);
     group.sampling_mode(SamplingMode::Flat);
@@ -244,7 +636,7 @@ fn beacon_state(c: &mut Criterion) {
     group.bench_with_input(
         BenchmarkId::new("Sszb", "encode"),
         &beacon_state,
-        |b, state: &BeaconState| {
+        |b, state: &BeaconState<S>| {
             let len = SszEncode::ssz_bytes_len(state);
             let mut buf: Vec<u8> = vec![0u8; len];
             b.iter(|| state.ssz_write(&mut buf.as_mut_slice()))
@@ -256,12 +648,11 @@ fn beacon_state(c: &mut Criterion) {
         BenchmarkId::new("Sszb", "decode"),
         state_bytes.as_slice(),
         |b, bytes| {
-            // b.iter(|| <BeaconState as SszDecode>::from_ssz_bytes(bytes).unwrap())
             b.iter_batched_ref(
                 || bytes.clone(),
                 |bytes| {
-                    let state =
-                        <BeaconState as SszDecode>::from_ssz_bytes(bytes).expect("should decode");
+                    let state = BeaconState::<S>::from_ssz_bytes_for_fork(bytes, fork)
+                        .expect("should decode");
                     black_box(state)
                 },
                 BatchSize::SmallInput,
@@ -280,6 +671,8 @@ fn beacon_state(c: &mut Criterion) {
     //     },
     // );
 
+    #[cfg(feature = "sigp")]
+    let spec = S::sigp_spec();
     #[cfg(feature = "sigp")]
     group.bench_with_input(
         BenchmarkId::new("Lighthouse", "decode"),
@@ -292,9 +685,8 @@ fn beacon_state(c: &mut Criterion) {
             b.iter_batched_ref(
                 || bytes.clone(),
                 |bytes| {
-                    let state: SigpBeaconState<MainnetEthSpec> =
-                        SigpBeaconState::from_ssz_bytes(&bytes, &ChainSpec::default())
-                            .expect("should decode");
+                    let state: SigpBeaconState<S::Sigp> =
+                        SigpBeaconState::from_ssz_bytes(&bytes, &spec).expect("should decode");
                     black_box(state)
                 },
                 BatchSize::SmallInput,
@@ -303,25 +695,411 @@ fn beacon_state(c: &mut Criterion) {
     );
 
     #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        let config = S::grandine_config();
+        group.bench_with_input(
+            BenchmarkId::new("Grandine", "decode"),
+            state_bytes.as_slice(),
+            |b, bytes| {
+                b.iter(|| {
+                    GrandineBeaconState::<S::Grandine>::from_ssz_unchecked(&config, bytes).unwrap()
+                })
+            },
+        );
+    }
+
+    group.finish();
+
+    // every library caches node hashes in its persistent trees, so each
+    // iteration hashes a freshly decoded state rather than hitting the cache
+    let mut group = c.benchmark_group(group_name("BeaconState tree_hash", S::NETWORK));
+    group.throughput(Throughput::Bytes(state_bytes.len() as u64));
+    group.sample_size(10);
+    group.sampling_mode(SamplingMode::Flat);
+
+    #[cfg(feature = "sszb")]
     group.bench_with_input(
-        BenchmarkId::new("Grandine", "decode"),
+        BenchmarkId::new("Sszb", "hash_tree_root"),
         state_bytes.as_slice(),
         |b, bytes| {
-            b.iter(|| {
-                GrandineBeaconState::<Mainnet>::from_ssz_unchecked(&Config::mainnet(), bytes)
-                    .unwrap()
-            })
+            b.iter_batched(
+                || BeaconState::<S>::from_ssz_bytes_for_fork(bytes, fork).unwrap(),
+                |state| state.tree_hash_root(),
+                BatchSize::LargeInput,
+            )
+        },
+    );
+
+    #[cfg(feature = "sigp")]
+    group.bench_with_input(
+        BenchmarkId::new("Lighthouse", "hash_tree_root"),
+        state_bytes.as_slice(),
+        |b, bytes| {
+            b.iter_batched(
+                || SigpBeaconState::<S::Sigp>::from_ssz_bytes(bytes, &spec).unwrap(),
+                |mut state| state.update_tree_hash_cache().unwrap(),
+                BatchSize::LargeInput,
+            )
         },
     );
 
+    #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        let config = S::grandine_config();
+        group.bench_with_input(
+            BenchmarkId::new("Grandine", "hash_tree_root"),
+            state_bytes.as_slice(),
+            |b, bytes| {
+                b.iter_batched(
+                    || {
+                        GrandineBeaconState::<S::Grandine>::from_ssz_unchecked(&config, bytes)
+                            .unwrap()
+                    },
+                    |state| state.hash_tree_root(),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+#[cfg(feature = "block")]
+fn beacon_block_synthetic(c: &mut Criterion) {
+    match Network::from_env() {
+        Ok(network) => with_arena_spec!(network, S => bench_beacon_block_synthetic::<S>(c)),
+        Err(e) => eprintln!("skipping synthetic SignedBeaconBlock benchmarks: {e}"),
+    }
+}
+
+/// Decode of a typical block against one with every operation list at its bound.
+#[cfg(feature = "block")]
+fn bench_beacon_block_synthetic<S: ArenaSpec>(c: &mut Criterion) {
+    use ssz_arena::{verify_block, BlockGenerator, ForkName};
+
+    let mut group = c.benchmark_group(group_name("SignedBeaconBlock synthetic", S::NETWORK));
+
+    for (shape, generator) in [
+        ("typical", BlockGenerator::new(0)),
+        ("worst case", BlockGenerator::worst_case(0)),
+    ] {
+        let block = match generator.generate::<S::Preset>(S::NETWORK) {
+            Ok(block) => block,
+            Err(e) => {
+                eprintln!("skipping {shape} SignedBeaconBlock: {e}");
+                continue;
+            }
+        };
+        let fork = S::NETWORK.fork_at_slot(block.message.slot);
+        if fork != ForkName::Deneb {
+            eprintln!(
+                "skipping synthetic SignedBeaconBlock benchmarks: Deneb isn't scheduled on {}",
+                S::NETWORK
+            );
+            return;
+        }
+        let block_bytes = sszb::SszEncode::to_ssz(&block);
+        drop(block);
+        if let Err(e) = verify_block::<S>(&block_bytes) {
+            panic!("{shape} SignedBeaconBlock verification failed: {e}");
+        }
+        group.throughput(Throughput::Bytes(block_bytes.len() as u64));
+
+        #[cfg(feature = "sszb")]
+        group.bench_with_input(
+            BenchmarkId::new("Sszb", shape),
+            block_bytes.as_slice(),
+            |b, bytes| {
+                b.iter(|| {
+                    ssz_arena::SignedBeaconBlockAnyFork::<S::Preset>::from_ssz_bytes_for_fork(
+                        bytes, fork,
+                    )
+                    .unwrap()
+                })
+            },
+        );
+
+        #[cfg(feature = "sigp")]
+        group.bench_with_input(
+            BenchmarkId::new("Lighthouse", shape),
+            block_bytes.as_slice(),
+            |b, bytes| {
+                b.iter(|| {
+                    SigpBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(bytes, fork.into()).unwrap()
+                })
+            },
+        );
+
+        #[cfg(feature = "grandine")]
+        if S::GRANDINE_SUPPORTED {
+            let config = S::grandine_config();
+            group.bench_with_input(
+                BenchmarkId::new("Grandine", shape),
+                block_bytes.as_slice(),
+                |b, bytes| {
+                    b.iter(|| {
+                        GrandineBeaconBlock::<S::Grandine>::from_ssz_unchecked(&config, bytes)
+                            .unwrap()
+                    })
+                },
+            );
+        }
+    }
+
     group.finish();
 }
 
 #[cfg(feature = "block")]
-criterion_group!(light_benches, basic_types, container_types, beacon_block);
+fn beacon_block_malformed(c: &mut Criterion) {
+    match Network::from_env() {
+        Ok(network) => with_arena_spec!(network, S => {
+            if let Some(bytes) = load_or_skip("malformed SignedBeaconBlock", ssz_arena::load_block_bytes) {
+                bench_malformed::<S>(c, ssz_arena::FixtureKind::Block, &bytes);
+            }
+        }),
+        Err(e) => eprintln!("skipping malformed SignedBeaconBlock benchmarks: {e}"),
+    }
+}
+
+#[cfg(feature = "state")]
+fn beacon_state_malformed(c: &mut Criterion) {
+    match Network::from_env() {
+        Ok(network) => with_arena_spec!(network, S => {
+            if let Some(bytes) = load_or_skip("malformed BeaconState", ssz_arena::load_state_bytes) {
+                bench_malformed::<S>(c, ssz_arena::FixtureKind::State, &bytes);
+            }
+        }),
+        Err(e) => eprintln!("skipping malformed BeaconState benchmarks: {e}"),
+    }
+}
+
+/// How fast each library rejects the fixture corrupted by every mutation.
+/// A library that accepts or panics on any of them fails the run.
+#[cfg(any(feature = "block", feature = "state"))]
+fn bench_malformed<S: ArenaSpec>(c: &mut Criterion, kind: ssz_arena::FixtureKind, valid: &[u8]) {
+    use ssz_arena::{check_rejected, malform, peek_slot, FixtureKind, Mutation};
+
+    let object = match kind {
+        FixtureKind::Block => "SignedBeaconBlock",
+        FixtureKind::State => "BeaconState",
+    };
+    let Some(slot) = peek_slot(kind, valid) else {
+        eprintln!("skipping malformed {object} benchmarks: fixture too short to contain a slot");
+        return;
+    };
+    let fork = S::NETWORK.fork_at_slot(slot);
+
+    let mut group = c.benchmark_group(group_name(&format!("{object} malformed"), S::NETWORK));
+    if kind == FixtureKind::State {
+        group.sample_size(10);
+        group.sampling_mode(SamplingMode::Flat);
+    }
+
+    // one mutation at a time, a state fixture is too large to hold every copy
+    for mutation in Mutation::ALL {
+        let Some(bytes) = malform::<S>(kind, valid, mutation) else {
+            eprintln!("skipping {object} {mutation}: the fixture has nothing to corrupt that way");
+            continue;
+        };
+        if let Err(e) = check_rejected::<S>(kind, &bytes, fork) {
+            panic!("{object} {mutation}: {e}");
+        }
+        let id = mutation.as_str();
+
+        #[cfg(feature = "sszb")]
+        group.bench_with_input(
+            BenchmarkId::new("Sszb", id),
+            bytes.as_slice(),
+            |b, bytes| {
+                b.iter(|| match kind {
+                    FixtureKind::Block => {
+                        ssz_arena::SignedBeaconBlockAnyFork::<S::Preset>::from_ssz_bytes_for_fork(
+                            bytes, fork,
+                        )
+                        .is_err()
+                    }
+                    FixtureKind::State => {
+                        ssz_arena::BeaconStateAnyFork::<S::Preset>::from_ssz_bytes_for_fork(
+                            bytes, fork,
+                        )
+                        .is_err()
+                    }
+                })
+            },
+        );
+
+        #[cfg(feature = "sigp")]
+        {
+            let spec = S::sigp_spec();
+            group.bench_with_input(
+                BenchmarkId::new("Lighthouse", id),
+                bytes.as_slice(),
+                |b, bytes| {
+                    b.iter(|| match kind {
+                        FixtureKind::Block => {
+                            sigp_types::SignedBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(
+                                bytes,
+                                fork.into(),
+                            )
+                            .is_err()
+                        }
+                        FixtureKind::State => {
+                            sigp_types::BeaconState::<S::Sigp>::from_ssz_bytes(bytes, &spec)
+                                .is_err()
+                        }
+                    })
+                },
+            );
+        }
+
+        #[cfg(feature = "grandine")]
+        if S::GRANDINE_SUPPORTED {
+            let config = S::grandine_config();
+            group.bench_with_input(
+                BenchmarkId::new("Grandine", id),
+                bytes.as_slice(),
+                |b, bytes| {
+                    b.iter(|| match kind {
+                        FixtureKind::Block => grandine_types::combined::SignedBeaconBlock::<
+                            S::Grandine,
+                        >::from_ssz(&config, bytes)
+                        .is_err(),
+                        FixtureKind::State => {
+                            grandine_types::combined::BeaconState::<S::Grandine>::from_ssz(
+                                &config, bytes,
+                            )
+                            .is_err()
+                        }
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+#[cfg(feature = "block")]
+criterion_group!(
+    light_benches,
+    basic_types,
+    container_types,
+    tree_hash_types,
+    transactions,
+    beacon_block,
+    beacon_block_synthetic,
+    beacon_block_malformed
+);
 
 #[cfg(not(feature = "block"))]
-criterion_group!(light_benches, basic_types, container_types);
+criterion_group!(
+    light_benches,
+    basic_types,
+    container_types,
+    tree_hash_types,
+    transactions
+);
+
+#[cfg(feature = "state")]
+fn beacon_state_scaling(c: &mut Criterion) {
+    match Network::from_env() {
+        Ok(network) => with_arena_spec!(network, S => bench_beacon_state_scaling::<S>(c)),
+        Err(e) => eprintln!("skipping BeaconState scaling benchmarks: {e}"),
+    }
+}
+
+/// Decode time of synthetic Deneb states against their validator count, from
+/// `SSZ_ARENA_SCALING_VALIDATORS` (comma separated) or 1k to 2M.
+#[cfg(feature = "state")]
+fn bench_beacon_state_scaling<S: ArenaSpec>(c: &mut Criterion) {
+    use ssz_arena::StateGenerator;
+
+    #[cfg(feature = "sigp")]
+    use sigp_types::BeaconState as SigpBeaconState;
+
+    let counts = match std::env::var("SSZ_ARENA_SCALING_VALIDATORS") {
+        Ok(counts) => counts
+            .split(',')
+            .map(|count| count.trim().parse())
+            .collect::<Result<Vec<usize>, _>>(),
+        Err(_) => Ok(vec![1_000, 10_000, 100_000, 1_000_000, 2_000_000]),
+    };
+    let counts = match counts {
+        Ok(counts) => counts,
+        Err(e) => {
+            eprintln!("skipping BeaconState scaling benchmarks: SSZ_ARENA_SCALING_VALIDATORS must be comma separated integers: {e}");
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group(group_name("BeaconState scaling", S::NETWORK));
+    group.sample_size(10);
+    group.sampling_mode(SamplingMode::Flat);
+
+    for count in counts {
+        let state = match StateGenerator::new(0)
+            .with_validators(count)
+            .generate::<S::Preset>(S::NETWORK)
+        {
+            Ok(state) => state,
+            Err(e) => {
+                eprintln!("skipping {count} validators: {e}");
+                continue;
+            }
+        };
+        if S::NETWORK.fork_at_slot(state.slot) != ssz_arena::ForkName::Deneb {
+            eprintln!(
+                "skipping BeaconState scaling benchmarks: Deneb isn't scheduled on {}",
+                S::NETWORK
+            );
+            return;
+        }
+        let state_bytes = state.to_ssz();
+        drop(state);
+        group.throughput(Throughput::Bytes(state_bytes.len() as u64));
+
+        #[cfg(feature = "sszb")]
+        group.bench_with_input(
+            BenchmarkId::new("Sszb", count),
+            state_bytes.as_slice(),
+            |b, bytes| {
+                b.iter(|| {
+                    <ssz_arena::BeaconState<S::Preset> as SszDecode>::from_ssz_bytes(bytes).unwrap()
+                })
+            },
+        );
+
+        #[cfg(feature = "sigp")]
+        {
+            let spec = S::sigp_spec();
+            group.bench_with_input(
+                BenchmarkId::new("Lighthouse", count),
+                state_bytes.as_slice(),
+                |b, bytes| {
+                    b.iter(|| SigpBeaconState::<S::Sigp>::from_ssz_bytes(bytes, &spec).unwrap())
+                },
+            );
+        }
+
+        #[cfg(feature = "grandine")]
+        if S::GRANDINE_SUPPORTED {
+            let config = S::grandine_config();
+            group.bench_with_input(
+                BenchmarkId::new("Grandine", count),
+                state_bytes.as_slice(),
+                |b, bytes| {
+                    b.iter(|| {
+                        GrandineBeaconState::<S::Grandine>::from_ssz_unchecked(&config, bytes)
+                            .unwrap()
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
 
 // so-called heavy bench because BeaconState requires more time to benchmark than basic types and beacon blocks
 // we use a different Criterion setup to account for this
@@ -330,7 +1108,7 @@ criterion_group!(light_benches, basic_types, container_types);
 criterion_group! {
     name = heavy_benches;
     config = Criterion::default(); // TODO: add more time
-    targets = beacon_state
+    targets = beacon_state, beacon_state_scaling, beacon_state_malformed
 }
 
 #[cfg(not(feature = "state"))]
@@ -0,0 +1,25 @@
+#![no_main]
+
+//! The first byte picks the fork, the rest is the state. Minimal preset, whose
+//! fixed part is around 10 KB rather than mainnet's 2.7 MB. Run with a
+//! `-max_len` above that.
+
+use libfuzzer_sys::fuzz_target;
+use ssz_arena::{BeaconStateAnyFork, ForkName, MinimalPreset};
+use sszb::SszEncode;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((fork, bytes)) = data.split_first() else {
+        return;
+    };
+    let fork = ForkName::ALL[*fork as usize % ForkName::ALL.len()];
+    let Ok(state) = BeaconStateAnyFork::<MinimalPreset>::from_ssz_bytes_for_fork(bytes, fork)
+    else {
+        return;
+    };
+    assert_eq!(
+        state.to_ssz(),
+        bytes,
+        "{fork} BeaconState round-trip isn't canonical"
+    );
+});
@@ -0,0 +1,109 @@
+#![no_main]
+
+//! Feeds the same bytes to sszb, lighthouse's ethereum_ssz and grandine, and
+//! fails when one accepts what another rejects or two decode to different
+//! values. The first byte picks the type, the rest is its encoding.
+
+use std::fmt::Debug;
+
+use grandine_ssz::{PersistentList, SszHash, SszReadDefault};
+use libfuzzer_sys::fuzz_target;
+use ssz_arena::ExecutionPayloadHeader;
+use tree_hash::TreeHash;
+
+/// Small, so lists past their limit are within the fuzzer's reach.
+type Limit = typenum::U1024;
+type GrandineHeader =
+    grandine_types::deneb::containers::ExecutionPayloadHeader<grandine_types::preset::Mainnet>;
+
+/// A decode's `hash_tree_root`, which stands in for its value across the
+/// libraries' different types, or its error.
+fn outcome<T, E: Debug>(
+    decoded: Result<T, E>,
+    root: impl FnOnce(&T) -> [u8; 32],
+) -> Result<[u8; 32], String> {
+    decoded.as_ref().map(root).map_err(|e| format!("{e:?}"))
+}
+
+fn check(object: &str, outcomes: [(&str, Result<[u8; 32], String>); 3]) {
+    let [(reference, expected), others @ ..] = &outcomes;
+    for (library, outcome) in others {
+        match (expected, outcome) {
+            (Ok(expected), Ok(root)) => assert_eq!(
+                root, expected,
+                "{object}: {library} decoded a different value than {reference}"
+            ),
+            (Err(_), Err(_)) => {}
+            (Ok(_), Err(e)) => panic!("{object}: {reference} accepted, {library} rejected: {e}"),
+            (Err(e), Ok(_)) => panic!("{object}: {library} accepted, {reference} rejected: {e}"),
+        }
+    }
+}
+
+fn list(bytes: &[u8]) {
+    type List = milhouse::List<u64, Limit>;
+
+    check(
+        "List<u64>",
+        [
+            (
+                "sszb",
+                outcome(<List as sszb::SszDecode>::from_ssz_bytes(bytes), |list| {
+                    list.tree_hash_root().0
+                }),
+            ),
+            (
+                "lighthouse",
+                outcome(<List as ssz::Decode>::from_ssz_bytes(bytes), |list| {
+                    list.tree_hash_root().0
+                }),
+            ),
+            (
+                "grandine",
+                outcome(
+                    PersistentList::<u64, Limit>::from_ssz_default(bytes),
+                    |list| list.hash_tree_root().0,
+                ),
+            ),
+        ],
+    );
+}
+
+fn execution_payload_header(bytes: &[u8]) {
+    check(
+        "ExecutionPayloadHeader",
+        [
+            (
+                "sszb",
+                outcome(
+                    <ExecutionPayloadHeader as sszb::SszDecode>::from_ssz_bytes(bytes),
+                    |header| header.tree_hash_root().0,
+                ),
+            ),
+            (
+                "lighthouse",
+                outcome(
+                    <ExecutionPayloadHeader as ssz::Decode>::from_ssz_bytes(bytes),
+                    |header| header.tree_hash_root().0,
+                ),
+            ),
+            (
+                "grandine",
+                outcome(GrandineHeader::from_ssz_default(bytes), |header| {
+                    header.hash_tree_root().0
+                }),
+            ),
+        ],
+    );
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((object, bytes)) = data.split_first() else {
+        return;
+    };
+    if object % 2 == 0 {
+        list(bytes);
+    } else {
+        execution_payload_header(bytes);
+    }
+});
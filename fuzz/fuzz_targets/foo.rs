@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ssz::{Decode, Encode};
+use ssz_arena::Foo;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(foo) = Foo::from_ssz_bytes(data) else {
+        return;
+    };
+    assert_eq!(foo.as_ssz_bytes(), data, "Foo round-trip isn't canonical");
+});
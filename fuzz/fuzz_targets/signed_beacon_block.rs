@@ -0,0 +1,24 @@
+#![no_main]
+
+//! The first byte picks the fork, the rest is the block. Minimal preset, so
+//! inputs stay within libFuzzer's usual lengths.
+
+use libfuzzer_sys::fuzz_target;
+use ssz_arena::{ForkName, MinimalPreset, SignedBeaconBlockAnyFork};
+use sszb::SszEncode;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((fork, bytes)) = data.split_first() else {
+        return;
+    };
+    let fork = ForkName::ALL[*fork as usize % ForkName::ALL.len()];
+    let Ok(block) = SignedBeaconBlockAnyFork::<MinimalPreset>::from_ssz_bytes_for_fork(bytes, fork)
+    else {
+        return;
+    };
+    assert_eq!(
+        block.to_ssz(),
+        bytes,
+        "{fork} SignedBeaconBlock round-trip isn't canonical"
+    );
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ssz_arena::TxOpaque;
+use sszb::{SszDecode, SszEncode};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(txs) = TxOpaque::from_ssz_bytes(data) else {
+        return;
+    };
+    assert_eq!(txs.to_ssz(), data, "TxOpaque round-trip isn't canonical");
+    // offsets that decoded but don't tile the buffer would cut iteration short
+    assert_eq!(txs.iter().count(), txs.len());
+    assert_eq!(
+        txs.iter().map(<[u8]>::len).sum::<usize>(),
+        txs.total_bytes()
+    );
+});
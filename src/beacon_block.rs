@@ -1,14 +1,13 @@
 use alloy_primitives::{Address, FixedBytes, B256, U256};
 use bytes::buf::{Buf, BufMut};
 use itertools::Itertools as _;
-use sigp_types::TransactionsOpaque;
 use ssz_types::{BitList, BitVector, FixedVector, VariableList as List};
 use sszb::*;
 use sszb_derive::{SszbDecode, SszbEncode};
 use tree_hash::*;
 use tree_hash_derive::TreeHash;
 
-use crate::TxOpaque;
+use crate::{peek_slot, FixtureKind, ForkName, MainnetPreset, Network, Preset, TxOpaque};
 
 type ByteList<N> = List<u8, N>;
 type ByteVector<N> = FixedVector<u8, N>;
@@ -18,18 +17,22 @@ type KZGCommitment = [u8; 48];
 type H160 = Address;
 type H256 = B256;
 
-#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug)]
-pub struct SignedBeaconBlock {
-    pub message: BeaconBlock,
+/// Deneb block, the layout the arena benchmarks by default.
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct SignedBeaconBlock<P: Preset = MainnetPreset> {
+    pub message: BeaconBlock<P>,
     pub signature: Sig,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct SignedBeaconBlockHeader {
     pub message: BeaconBlockHeader,
     pub signature: Sig,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct BeaconBlockHeader {
     pub slot: u64,
@@ -39,17 +42,19 @@ pub struct BeaconBlockHeader {
     pub body_root: H256,
 }
 
-#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug)]
-pub struct BeaconBlock {
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlock<P: Preset = MainnetPreset> {
     pub slot: u64,
     pub proposer_index: u64,
     pub parent_root: H256,
     pub state_root: H256,
-    pub body: BeaconBlockBody,
+    pub body: BeaconBlockBody<P>,
 }
 
-#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug)]
-pub struct BeaconBlockBody {
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockBody<P: Preset = MainnetPreset> {
     pub randao_reveal: Sig,
     pub eth1_data: Eth1Data,
     pub graffiti: FixedBytes<32>,
@@ -58,31 +63,35 @@ pub struct BeaconBlockBody {
     pub attestations: List<Attestation, typenum::U128>,
     pub deposits: List<Deposit, typenum::U16>,
     pub voluntary_exits: List<SignedVoluntaryExit, typenum::U16>,
-    pub sync_aggregate: SyncAggregate,
-    pub execution_payload: ExecutionPayload,
+    pub sync_aggregate: SyncAggregate<P>,
+    pub execution_payload: ExecutionPayload<P>,
     pub bls_to_execution_changes: List<SignedBlsToExecutionChange, typenum::U16>,
-    pub blob_kzg_commitments: List<KZGCommitment, typenum::U4096>,
+    pub blob_kzg_commitments: List<KZGCommitment, P::MaxBlobCommitmentsPerBlock>,
 }
 
-#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug)]
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct Eth1Data {
     pub deposit_root: H256,
     pub deposit_count: u64,
     pub block_hash: H256,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct ProposerSlashing {
     pub signed_header_1: SignedBeaconBlockHeader,
     pub signed_header_2: SignedBeaconBlockHeader,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct Checkpoint {
     pub epoch: u64,
     pub root: H256,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct AttestationData {
     pub slot: u64,
@@ -92,6 +101,7 @@ pub struct AttestationData {
     pub target: Checkpoint,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct IndexedAttestation {
     pub attesting_indices: List<u64, typenum::U2048>,
@@ -99,12 +109,14 @@ pub struct IndexedAttestation {
     pub signature: Sig,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct AttesterSlashing {
     pub attestation_1: IndexedAttestation,
     pub attestation_2: IndexedAttestation,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct Attestation {
     pub aggregation_bits: BitList<typenum::U2048>,
@@ -112,6 +124,7 @@ pub struct Attestation {
     pub signature: Sig,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct DepositData {
     pub pubkey: PublicKeyBytes,
@@ -120,32 +133,35 @@ pub struct DepositData {
     pub signature: Sig,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct Deposit {
-    pub proof: FixedVector<H256, typenum::U32>,
+    pub proof: FixedVector<H256, typenum::U33>,
     pub data: DepositData,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct VoluntaryExit {
     pub epoch: u64,
     pub validator_index: u64,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct SignedVoluntaryExit {
     pub message: VoluntaryExit,
     pub signature: Sig,
 }
 
-#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug)]
-pub struct SyncAggregate {
-    pub sync_committee_bits: BitVector<typenum::U512>,
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct SyncAggregate<P: Preset = MainnetPreset> {
+    pub sync_committee_bits: BitVector<P::SyncCommitteeSize>,
     pub sync_committee_signature: Sig,
 }
 
-pub type Transaction = ByteList<typenum::U1073741824>;
-
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct Withdrawal {
     pub index: u64,
@@ -154,13 +170,14 @@ pub struct Withdrawal {
     pub amount: u64,
 }
 
-#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug)]
-pub struct ExecutionPayload {
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct ExecutionPayload<P: Preset = MainnetPreset> {
     pub parent_hash: H256,
     pub fee_recipient: H160,
     pub state_root: H256,
     pub receipts_root: H256,
-    pub logs_bloom: FixedBytes<256>,
+    pub logs_bloom: ByteVector<typenum::U256>,
     pub prev_randao: H256,
     pub block_number: u64,
     pub gas_limit: u64,
@@ -171,22 +188,455 @@ pub struct ExecutionPayload {
     pub block_hash: H256,
     // pub transactions: List<Transaction, typenum::U1048576>,
     pub transactions: TxOpaque,
-    pub withdrawals: List<Withdrawal, typenum::U16>,
+    pub withdrawals: List<Withdrawal, P::MaxWithdrawalsPerPayload>,
 
     // New in Deneb
     pub blob_gas_used: u64,
     pub excess_blob_gas: u64,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct SignedBlsToExecutionChange {
     pub message: BlsToExecutionChange,
     pub signature: Sig,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct BlsToExecutionChange {
     pub validator_index: u64,
     pub from_bls_pubkey: PublicKeyBytes,
     pub to_execution_address: H160,
 }
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct ExecutionPayloadBellatrix {
+    pub parent_hash: H256,
+    pub fee_recipient: H160,
+    pub state_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: ByteVector<typenum::U256>,
+    pub prev_randao: H256,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: ByteList<typenum::U32>,
+    pub base_fee_per_gas: U256,
+    pub block_hash: H256,
+    pub transactions: TxOpaque,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct ExecutionPayloadCapella<P: Preset = MainnetPreset> {
+    pub parent_hash: H256,
+    pub fee_recipient: H160,
+    pub state_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: ByteVector<typenum::U256>,
+    pub prev_randao: H256,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: ByteList<typenum::U32>,
+    pub base_fee_per_gas: U256,
+    pub block_hash: H256,
+    pub transactions: TxOpaque,
+    pub withdrawals: List<Withdrawal, P::MaxWithdrawalsPerPayload>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockBodyPhase0 {
+    pub randao_reveal: Sig,
+    pub eth1_data: Eth1Data,
+    pub graffiti: FixedBytes<32>,
+    pub proposer_slashings: List<ProposerSlashing, typenum::U16>,
+    pub attester_slashings: List<AttesterSlashing, typenum::U2>,
+    pub attestations: List<Attestation, typenum::U128>,
+    pub deposits: List<Deposit, typenum::U16>,
+    pub voluntary_exits: List<SignedVoluntaryExit, typenum::U16>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockBodyAltair<P: Preset = MainnetPreset> {
+    pub randao_reveal: Sig,
+    pub eth1_data: Eth1Data,
+    pub graffiti: FixedBytes<32>,
+    pub proposer_slashings: List<ProposerSlashing, typenum::U16>,
+    pub attester_slashings: List<AttesterSlashing, typenum::U2>,
+    pub attestations: List<Attestation, typenum::U128>,
+    pub deposits: List<Deposit, typenum::U16>,
+    pub voluntary_exits: List<SignedVoluntaryExit, typenum::U16>,
+    pub sync_aggregate: SyncAggregate<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockBodyBellatrix<P: Preset = MainnetPreset> {
+    pub randao_reveal: Sig,
+    pub eth1_data: Eth1Data,
+    pub graffiti: FixedBytes<32>,
+    pub proposer_slashings: List<ProposerSlashing, typenum::U16>,
+    pub attester_slashings: List<AttesterSlashing, typenum::U2>,
+    pub attestations: List<Attestation, typenum::U128>,
+    pub deposits: List<Deposit, typenum::U16>,
+    pub voluntary_exits: List<SignedVoluntaryExit, typenum::U16>,
+    pub sync_aggregate: SyncAggregate<P>,
+    pub execution_payload: ExecutionPayloadBellatrix,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockBodyCapella<P: Preset = MainnetPreset> {
+    pub randao_reveal: Sig,
+    pub eth1_data: Eth1Data,
+    pub graffiti: FixedBytes<32>,
+    pub proposer_slashings: List<ProposerSlashing, typenum::U16>,
+    pub attester_slashings: List<AttesterSlashing, typenum::U2>,
+    pub attestations: List<Attestation, typenum::U128>,
+    pub deposits: List<Deposit, typenum::U16>,
+    pub voluntary_exits: List<SignedVoluntaryExit, typenum::U16>,
+    pub sync_aggregate: SyncAggregate<P>,
+    pub execution_payload: ExecutionPayloadCapella<P>,
+    pub bls_to_execution_changes: List<SignedBlsToExecutionChange, typenum::U16>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockPhase0 {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body: BeaconBlockBodyPhase0,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockAltair<P: Preset = MainnetPreset> {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body: BeaconBlockBodyAltair<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockBellatrix<P: Preset = MainnetPreset> {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body: BeaconBlockBodyBellatrix<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockCapella<P: Preset = MainnetPreset> {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body: BeaconBlockBodyCapella<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct SignedBeaconBlockPhase0 {
+    pub message: BeaconBlockPhase0,
+    pub signature: Sig,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct SignedBeaconBlockAltair<P: Preset = MainnetPreset> {
+    pub message: BeaconBlockAltair<P>,
+    pub signature: Sig,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct SignedBeaconBlockBellatrix<P: Preset = MainnetPreset> {
+    pub message: BeaconBlockBellatrix<P>,
+    pub signature: Sig,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct SignedBeaconBlockCapella<P: Preset = MainnetPreset> {
+    pub message: BeaconBlockCapella<P>,
+    pub signature: Sig,
+}
+
+pub type SignedBeaconBlockDeneb<P = MainnetPreset> = SignedBeaconBlock<P>;
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct AttestationElectra<P: Preset = MainnetPreset> {
+    pub aggregation_bits: BitList<P::MaxValidatorsPerSlot>,
+    pub data: AttestationData,
+    pub signature: Sig,
+    pub committee_bits: BitVector<P::MaxCommitteesPerSlot>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct IndexedAttestationElectra<P: Preset = MainnetPreset> {
+    pub attesting_indices: List<u64, P::MaxValidatorsPerSlot>,
+    pub data: AttestationData,
+    pub signature: Sig,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct AttesterSlashingElectra<P: Preset = MainnetPreset> {
+    pub attestation_1: IndexedAttestationElectra<P>,
+    pub attestation_2: IndexedAttestationElectra<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct DepositRequest {
+    pub pubkey: PublicKeyBytes,
+    pub withdrawal_credentials: H256,
+    pub amount: u64,
+    pub signature: Sig,
+    pub index: u64,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct WithdrawalRequest {
+    pub source_address: H160,
+    pub validator_pubkey: PublicKeyBytes,
+    pub amount: u64,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct ConsolidationRequest {
+    pub source_address: H160,
+    pub source_pubkey: PublicKeyBytes,
+    pub target_pubkey: PublicKeyBytes,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct ExecutionRequests<P: Preset = MainnetPreset> {
+    pub deposits: List<DepositRequest, P::MaxDepositRequestsPerPayload>,
+    pub withdrawals: List<WithdrawalRequest, P::MaxWithdrawalRequestsPerPayload>,
+    pub consolidations: List<ConsolidationRequest, P::MaxConsolidationRequestsPerPayload>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockBodyElectra<P: Preset = MainnetPreset> {
+    pub randao_reveal: Sig,
+    pub eth1_data: Eth1Data,
+    pub graffiti: FixedBytes<32>,
+    pub proposer_slashings: List<ProposerSlashing, typenum::U16>,
+    pub attester_slashings: List<AttesterSlashingElectra<P>, typenum::U1>,
+    pub attestations: List<AttestationElectra<P>, typenum::U8>,
+    pub deposits: List<Deposit, typenum::U16>,
+    pub voluntary_exits: List<SignedVoluntaryExit, typenum::U16>,
+    pub sync_aggregate: SyncAggregate<P>,
+    pub execution_payload: ExecutionPayload<P>,
+    pub bls_to_execution_changes: List<SignedBlsToExecutionChange, typenum::U16>,
+    pub blob_kzg_commitments: List<KZGCommitment, P::MaxBlobCommitmentsPerBlock>,
+    pub execution_requests: ExecutionRequests<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconBlockElectra<P: Preset = MainnetPreset> {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body: BeaconBlockBodyElectra<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct SignedBeaconBlockElectra<P: Preset = MainnetPreset> {
+    pub message: BeaconBlockElectra<P>,
+    pub signature: Sig,
+}
+
+/// A block of any fork. SSZ carries no fork tag, so decoding needs the fork
+/// from the caller, the network's fork schedule or the `Eth-Consensus-Version`
+/// header, while encoding just writes the inner block.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SignedBeaconBlockAnyFork<P: Preset = MainnetPreset> {
+    Phase0(SignedBeaconBlockPhase0),
+    Altair(SignedBeaconBlockAltair<P>),
+    Bellatrix(SignedBeaconBlockBellatrix<P>),
+    Capella(SignedBeaconBlockCapella<P>),
+    Deneb(SignedBeaconBlockDeneb<P>),
+    Electra(SignedBeaconBlockElectra<P>),
+}
+
+impl<P: Preset> SignedBeaconBlockAnyFork<P> {
+    pub fn fork_name(&self) -> ForkName {
+        match self {
+            Self::Phase0(_) => ForkName::Phase0,
+            Self::Altair(_) => ForkName::Altair,
+            Self::Bellatrix(_) => ForkName::Bellatrix,
+            Self::Capella(_) => ForkName::Capella,
+            Self::Deneb(_) => ForkName::Deneb,
+            Self::Electra(_) => ForkName::Electra,
+        }
+    }
+
+    pub fn slot(&self) -> u64 {
+        match self {
+            Self::Phase0(block) => block.message.slot,
+            Self::Altair(block) => block.message.slot,
+            Self::Bellatrix(block) => block.message.slot,
+            Self::Capella(block) => block.message.slot,
+            Self::Deneb(block) => block.message.slot,
+            Self::Electra(block) => block.message.slot,
+        }
+    }
+
+    /// The execution payload's transactions, `None` before Bellatrix.
+    pub fn transactions(&self) -> Option<&TxOpaque> {
+        match self {
+            Self::Phase0(_) | Self::Altair(_) => None,
+            Self::Bellatrix(block) => Some(&block.message.body.execution_payload.transactions),
+            Self::Capella(block) => Some(&block.message.body.execution_payload.transactions),
+            Self::Deneb(block) => Some(&block.message.body.execution_payload.transactions),
+            Self::Electra(block) => Some(&block.message.body.execution_payload.transactions),
+        }
+    }
+
+    pub fn from_ssz_bytes_for_fork(bytes: &[u8], fork: ForkName) -> Result<Self, DecodeError> {
+        match fork {
+            ForkName::Phase0 => {
+                <SignedBeaconBlockPhase0 as SszDecode>::from_ssz_bytes(bytes).map(Self::Phase0)
+            }
+            ForkName::Altair => {
+                <SignedBeaconBlockAltair<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Altair)
+            }
+            ForkName::Bellatrix => {
+                <SignedBeaconBlockBellatrix<P> as SszDecode>::from_ssz_bytes(bytes)
+                    .map(Self::Bellatrix)
+            }
+            ForkName::Capella => {
+                <SignedBeaconBlockCapella<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Capella)
+            }
+            ForkName::Deneb => {
+                <SignedBeaconBlockDeneb<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Deneb)
+            }
+            ForkName::Electra => {
+                <SignedBeaconBlockElectra<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Electra)
+            }
+        }
+    }
+
+    /// Picks the fork from the block's slot and `network`'s fork schedule.
+    pub fn from_ssz_bytes_for_network(bytes: &[u8], network: Network) -> Result<Self, DecodeError> {
+        let slot = peek_slot(FixtureKind::Block, bytes).ok_or_else(|| {
+            DecodeError::BytesInvalid("block too short to contain a slot".to_string())
+        })?;
+        Self::from_ssz_bytes_for_fork(bytes, network.fork_at_slot(slot))
+    }
+
+    /// Picks the fork from an `Eth-Consensus-Version` header value.
+    pub fn from_ssz_bytes_with_consensus_version(
+        bytes: &[u8],
+        consensus_version: &str,
+    ) -> Result<Self, DecodeError> {
+        let fork = consensus_version
+            .parse()
+            .map_err(|e: crate::ArenaFetchError| DecodeError::BytesInvalid(e.to_string()))?;
+        Self::from_ssz_bytes_for_fork(bytes, fork)
+    }
+}
+
+impl<P: Preset> SszEncode for SignedBeaconBlockAnyFork<P> {
+    fn is_ssz_static() -> bool {
+        false
+    }
+
+    fn ssz_fixed_len() -> usize {
+        BYTES_PER_LENGTH_OFFSET
+    }
+
+    fn ssz_max_len() -> usize {
+        [
+            <SignedBeaconBlockPhase0 as SszEncode>::ssz_max_len(),
+            <SignedBeaconBlockAltair<P> as SszEncode>::ssz_max_len(),
+            <SignedBeaconBlockBellatrix<P> as SszEncode>::ssz_max_len(),
+            <SignedBeaconBlockCapella<P> as SszEncode>::ssz_max_len(),
+            <SignedBeaconBlockDeneb<P> as SszEncode>::ssz_max_len(),
+            <SignedBeaconBlockElectra<P> as SszEncode>::ssz_max_len(),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        match self {
+            Self::Phase0(block) => block.ssz_bytes_len(),
+            Self::Altair(block) => block.ssz_bytes_len(),
+            Self::Bellatrix(block) => block.ssz_bytes_len(),
+            Self::Capella(block) => block.ssz_bytes_len(),
+            Self::Deneb(block) => block.ssz_bytes_len(),
+            Self::Electra(block) => block.ssz_bytes_len(),
+        }
+    }
+
+    fn ssz_write_fixed(&self, offset: &mut usize, buf: &mut impl BufMut) {
+        buf.put_slice(&offset.to_le_bytes()[0..BYTES_PER_LENGTH_OFFSET]);
+        *offset += self.ssz_bytes_len();
+    }
+
+    fn ssz_write_variable(&self, buf: &mut impl BufMut) {
+        self.ssz_write(buf);
+    }
+
+    fn ssz_write(&self, buf: &mut impl BufMut) {
+        match self {
+            Self::Phase0(block) => block.ssz_write(buf),
+            Self::Altair(block) => block.ssz_write(buf),
+            Self::Bellatrix(block) => block.ssz_write(buf),
+            Self::Capella(block) => block.ssz_write(buf),
+            Self::Deneb(block) => block.ssz_write(buf),
+            Self::Electra(block) => block.ssz_write(buf),
+        }
+    }
+}
+
+impl<P: Preset> TreeHash for SignedBeaconBlockAnyFork<P> {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::Container
+    }
+
+    fn tree_hash_packed_encoding(&self) -> PackedEncoding {
+        unreachable!("Container should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("Container should never be packed.")
+    }
+
+    fn tree_hash_root(&self) -> Hash256 {
+        match self {
+            Self::Phase0(block) => block.tree_hash_root(),
+            Self::Altair(block) => block.tree_hash_root(),
+            Self::Bellatrix(block) => block.tree_hash_root(),
+            Self::Capella(block) => block.tree_hash_root(),
+            Self::Deneb(block) => block.tree_hash_root(),
+            Self::Electra(block) => block.tree_hash_root(),
+        }
+    }
+}
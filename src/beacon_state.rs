@@ -3,14 +3,18 @@ use bytes::buf::{Buf, BufMut};
 use ghilhouse::{List, Vector};
 use itertools::Itertools as _;
 use ssz_derive::Decode;
-use ssz_types::{BitVector, FixedVector, VariableList};
+use ssz_types::{BitList, BitVector, FixedVector, VariableList};
 use sszb::*;
 use sszb_derive::{SszbDecode, SszbEncode};
+use tree_hash::{Hash256, PackedEncoding, TreeHash, TreeHashType};
 use tree_hash_derive::TreeHash;
 
+use crate::{peek_slot, FixtureKind, ForkName, MainnetPreset, Network, Preset};
+
 type PublicKeyBytes = PKBytes; // [u8; 48];
 type H32 = [u8; 4];
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
 pub struct Fork {
     pub previous_version: H32,
@@ -18,12 +22,14 @@ pub struct Fork {
     pub epoch: u64,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
 pub struct Checkpoint {
     pub epoch: u64,
     pub root: B256,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
 pub struct BeaconBlockHeader {
     pub slot: u64,
@@ -33,6 +39,7 @@ pub struct BeaconBlockHeader {
     pub body_root: B256,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
 pub struct Eth1Data {
     pub deposit_root: B256,
@@ -40,6 +47,7 @@ pub struct Eth1Data {
     pub block_hash: B256,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
 pub struct Validator {
     pub pubkey: PublicKeyBytes,
@@ -52,12 +60,14 @@ pub struct Validator {
     pub withdrawable_epoch: u64,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
-pub struct SyncCommittee {
-    pub pubkeys: FixedVector<PublicKeyBytes, typenum::U512>,
+pub struct SyncCommittee<P: Preset = MainnetPreset> {
+    pub pubkeys: FixedVector<PublicKeyBytes, P::SyncCommitteeSize>,
     pub aggregate_pubkey: PublicKeyBytes,
 }
 
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default, Decode)]
 pub struct ExecutionPayloadHeader {
     pub parent_hash: B256,
@@ -79,14 +89,100 @@ pub struct ExecutionPayloadHeader {
     pub excess_blob_gas: u64,
 }
 
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
+pub struct ExecutionPayloadHeaderBellatrix {
+    pub parent_hash: B256,
+    pub fee_recipient: Address,
+    pub state_root: B256,
+    pub receipts_root: B256,
+    pub logs_bloom: FixedVector<u8, typenum::U256>,
+    pub prev_randao: B256,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: VariableList<u8, typenum::U32>,
+    pub base_fee_per_gas: U256,
+    pub block_hash: B256,
+    pub transactions_root: B256,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
+pub struct ExecutionPayloadHeaderCapella {
+    pub parent_hash: B256,
+    pub fee_recipient: Address,
+    pub state_root: B256,
+    pub receipts_root: B256,
+    pub logs_bloom: FixedVector<u8, typenum::U256>,
+    pub prev_randao: B256,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: VariableList<u8, typenum::U32>,
+    pub base_fee_per_gas: U256,
+    pub block_hash: B256,
+    pub transactions_root: B256,
+    pub withdrawals_root: B256,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
+pub struct AttestationData {
+    pub slot: u64,
+    pub index: u64,
+    pub beacon_block_root: B256,
+    pub source: Checkpoint,
+    pub target: Checkpoint,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct PendingAttestation {
+    pub aggregation_bits: BitList<typenum::U2048>,
+    pub data: AttestationData,
+    pub inclusion_delay: u64,
+    pub proposer_index: u64,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct PendingDeposit {
+    pub pubkey: PublicKeyBytes,
+    pub withdrawal_credentials: B256,
+    pub amount: u64,
+    pub signature: Sig,
+    pub slot: u64,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
+pub struct PendingPartialWithdrawal {
+    pub validator_index: u64,
+    pub amount: u64,
+    pub withdrawable_epoch: u64,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
+pub struct PendingConsolidation {
+    pub source_index: u64,
+    pub target_index: u64,
+}
+
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash, Default)]
 pub struct HistoricalSummary {
     pub block_summary_root: B256,
     pub state_summary_root: B256,
 }
 
+/// Deneb state, the layout the arena benchmarks by default.
+// schema.rs spells out this layout, change it with this struct
 #[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
-pub struct BeaconState {
+pub struct BeaconState<P: Preset = MainnetPreset> {
     // Versioning
     pub genesis_time: u64,
     pub genesis_validators_root: B256,
@@ -95,13 +191,13 @@ pub struct BeaconState {
 
     // History
     pub latest_block_header: BeaconBlockHeader,
-    pub block_roots: Vector<B256, typenum::U8192>,
-    pub state_roots: Vector<B256, typenum::U8192>,
+    pub block_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub state_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
     pub historical_roots: List<B256, typenum::U16777216>,
 
     // Ethereum 1.0 chain data
     pub eth1_data: Eth1Data,
-    pub eth1_data_votes: List<Eth1Data, typenum::U2048>,
+    pub eth1_data_votes: List<Eth1Data, P::SlotsPerEth1VotingPeriod>,
     pub eth1_deposit_index: u64,
 
     // Registry
@@ -109,10 +205,10 @@ pub struct BeaconState {
     pub balances: List<u64, typenum::U1099511627776>,
 
     // Randomness
-    pub randao_mixes: Vector<B256, typenum::U65536>,
+    pub randao_mixes: Vector<B256, P::EpochsPerHistoricalVector>,
 
     // Slashings
-    pub slashings: Vector<u64, typenum::U8192>,
+    pub slashings: Vector<u64, P::EpochsPerSlashingsVector>,
 
     // Participation (Altair and later)
     pub previous_epoch_participation: List<u8, typenum::U1099511627776>,
@@ -128,8 +224,8 @@ pub struct BeaconState {
     pub inactivity_scores: List<u64, typenum::U1099511627776>,
 
     // Light-client sync committees
-    pub current_sync_committee: SyncCommittee,
-    pub next_sync_committee: SyncCommittee,
+    pub current_sync_committee: SyncCommittee<P>,
+    pub next_sync_committee: SyncCommittee<P>,
 
     // Execution
     pub latest_execution_payload_header: ExecutionPayloadHeader,
@@ -141,3 +237,431 @@ pub struct BeaconState {
     // Deneb
     pub historical_summaries: List<HistoricalSummary, typenum::U16777216>,
 }
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconStatePhase0<P: Preset = MainnetPreset> {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub state_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub historical_roots: List<B256, typenum::U16777216>,
+
+    // Ethereum 1.0 chain data
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: List<Eth1Data, P::SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: List<Validator, typenum::U1099511627776>,
+    pub balances: List<u64, typenum::U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: Vector<B256, P::EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: Vector<u64, P::EpochsPerSlashingsVector>,
+
+    // Attestations
+    pub previous_epoch_attestations: List<PendingAttestation, P::MaxPendingAttestations>,
+    pub current_epoch_attestations: List<PendingAttestation, P::MaxPendingAttestations>,
+
+    // Finality
+    pub justification_bits: BitVector<typenum::U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconStateAltair<P: Preset = MainnetPreset> {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub state_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub historical_roots: List<B256, typenum::U16777216>,
+
+    // Ethereum 1.0 chain data
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: List<Eth1Data, P::SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: List<Validator, typenum::U1099511627776>,
+    pub balances: List<u64, typenum::U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: Vector<B256, P::EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: Vector<u64, P::EpochsPerSlashingsVector>,
+
+    // Participation
+    pub previous_epoch_participation: List<u8, typenum::U1099511627776>,
+    pub current_epoch_participation: List<u8, typenum::U1099511627776>,
+
+    // Finality
+    pub justification_bits: BitVector<typenum::U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    pub inactivity_scores: List<u64, typenum::U1099511627776>,
+
+    // Light-client sync committees
+    pub current_sync_committee: SyncCommittee<P>,
+    pub next_sync_committee: SyncCommittee<P>,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconStateBellatrix<P: Preset = MainnetPreset> {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub state_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub historical_roots: List<B256, typenum::U16777216>,
+
+    // Ethereum 1.0 chain data
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: List<Eth1Data, P::SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: List<Validator, typenum::U1099511627776>,
+    pub balances: List<u64, typenum::U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: Vector<B256, P::EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: Vector<u64, P::EpochsPerSlashingsVector>,
+
+    // Participation
+    pub previous_epoch_participation: List<u8, typenum::U1099511627776>,
+    pub current_epoch_participation: List<u8, typenum::U1099511627776>,
+
+    // Finality
+    pub justification_bits: BitVector<typenum::U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    pub inactivity_scores: List<u64, typenum::U1099511627776>,
+
+    // Light-client sync committees
+    pub current_sync_committee: SyncCommittee<P>,
+    pub next_sync_committee: SyncCommittee<P>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeaderBellatrix,
+}
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconStateCapella<P: Preset = MainnetPreset> {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub state_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub historical_roots: List<B256, typenum::U16777216>,
+
+    // Ethereum 1.0 chain data
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: List<Eth1Data, P::SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: List<Validator, typenum::U1099511627776>,
+    pub balances: List<u64, typenum::U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: Vector<B256, P::EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: Vector<u64, P::EpochsPerSlashingsVector>,
+
+    // Participation
+    pub previous_epoch_participation: List<u8, typenum::U1099511627776>,
+    pub current_epoch_participation: List<u8, typenum::U1099511627776>,
+
+    // Finality
+    pub justification_bits: BitVector<typenum::U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    pub inactivity_scores: List<u64, typenum::U1099511627776>,
+
+    // Light-client sync committees
+    pub current_sync_committee: SyncCommittee<P>,
+    pub next_sync_committee: SyncCommittee<P>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeaderCapella,
+
+    // Withdrawals
+    pub next_withdrawal_index: u64,
+    pub next_withdrawal_validator_index: u64,
+
+    // Deep history valid from Capella onwards
+    pub historical_summaries: List<HistoricalSummary, typenum::U16777216>,
+}
+
+pub type BeaconStateDeneb<P = MainnetPreset> = BeaconState<P>;
+
+// schema.rs spells out this layout, change it with this struct
+#[derive(Clone, SszbEncode, SszbDecode, PartialEq, Debug, TreeHash)]
+pub struct BeaconStateElectra<P: Preset = MainnetPreset> {
+    // Versioning
+    pub genesis_time: u64,
+    pub genesis_validators_root: B256,
+    pub slot: u64,
+    pub fork: Fork,
+
+    // History
+    pub latest_block_header: BeaconBlockHeader,
+    pub block_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub state_roots: Vector<B256, P::SlotsPerHistoricalRoot>,
+    pub historical_roots: List<B256, typenum::U16777216>,
+
+    // Ethereum 1.0 chain data
+    pub eth1_data: Eth1Data,
+    pub eth1_data_votes: List<Eth1Data, P::SlotsPerEth1VotingPeriod>,
+    pub eth1_deposit_index: u64,
+
+    // Registry
+    pub validators: List<Validator, typenum::U1099511627776>,
+    pub balances: List<u64, typenum::U1099511627776>,
+
+    // Randomness
+    pub randao_mixes: Vector<B256, P::EpochsPerHistoricalVector>,
+
+    // Slashings
+    pub slashings: Vector<u64, P::EpochsPerSlashingsVector>,
+
+    // Participation
+    pub previous_epoch_participation: List<u8, typenum::U1099511627776>,
+    pub current_epoch_participation: List<u8, typenum::U1099511627776>,
+
+    // Finality
+    pub justification_bits: BitVector<typenum::U4>,
+    pub previous_justified_checkpoint: Checkpoint,
+    pub current_justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+
+    // Inactivity
+    pub inactivity_scores: List<u64, typenum::U1099511627776>,
+
+    // Light-client sync committees
+    pub current_sync_committee: SyncCommittee<P>,
+    pub next_sync_committee: SyncCommittee<P>,
+
+    // Execution
+    pub latest_execution_payload_header: ExecutionPayloadHeader,
+
+    // Withdrawals
+    pub next_withdrawal_index: u64,
+    pub next_withdrawal_validator_index: u64,
+
+    // Deep history valid from Capella onwards
+    pub historical_summaries: List<HistoricalSummary, typenum::U16777216>,
+
+    // Electra
+    pub deposit_requests_start_index: u64,
+    pub deposit_balance_to_consume: u64,
+    pub exit_balance_to_consume: u64,
+    pub earliest_exit_epoch: u64,
+    pub consolidation_balance_to_consume: u64,
+    pub earliest_consolidation_epoch: u64,
+    pub pending_deposits: List<PendingDeposit, typenum::U134217728>,
+    pub pending_partial_withdrawals:
+        List<PendingPartialWithdrawal, P::PendingPartialWithdrawalsLimit>,
+    pub pending_consolidations: List<PendingConsolidation, P::PendingConsolidationsLimit>,
+}
+
+/// A state of any fork, decoded the same way as
+/// [`SignedBeaconBlockAnyFork`](crate::SignedBeaconBlockAnyFork).
+#[derive(Clone, PartialEq, Debug)]
+pub enum BeaconStateAnyFork<P: Preset = MainnetPreset> {
+    Phase0(BeaconStatePhase0<P>),
+    Altair(BeaconStateAltair<P>),
+    Bellatrix(BeaconStateBellatrix<P>),
+    Capella(BeaconStateCapella<P>),
+    Deneb(BeaconStateDeneb<P>),
+    Electra(BeaconStateElectra<P>),
+}
+
+impl<P: Preset> BeaconStateAnyFork<P> {
+    pub fn fork_name(&self) -> ForkName {
+        match self {
+            Self::Phase0(_) => ForkName::Phase0,
+            Self::Altair(_) => ForkName::Altair,
+            Self::Bellatrix(_) => ForkName::Bellatrix,
+            Self::Capella(_) => ForkName::Capella,
+            Self::Deneb(_) => ForkName::Deneb,
+            Self::Electra(_) => ForkName::Electra,
+        }
+    }
+
+    pub fn slot(&self) -> u64 {
+        match self {
+            Self::Phase0(state) => state.slot,
+            Self::Altair(state) => state.slot,
+            Self::Bellatrix(state) => state.slot,
+            Self::Capella(state) => state.slot,
+            Self::Deneb(state) => state.slot,
+            Self::Electra(state) => state.slot,
+        }
+    }
+
+    pub fn from_ssz_bytes_for_fork(bytes: &[u8], fork: ForkName) -> Result<Self, DecodeError> {
+        match fork {
+            ForkName::Phase0 => {
+                <BeaconStatePhase0<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Phase0)
+            }
+            ForkName::Altair => {
+                <BeaconStateAltair<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Altair)
+            }
+            ForkName::Bellatrix => {
+                <BeaconStateBellatrix<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Bellatrix)
+            }
+            ForkName::Capella => {
+                <BeaconStateCapella<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Capella)
+            }
+            ForkName::Deneb => {
+                <BeaconStateDeneb<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Deneb)
+            }
+            ForkName::Electra => {
+                <BeaconStateElectra<P> as SszDecode>::from_ssz_bytes(bytes).map(Self::Electra)
+            }
+        }
+    }
+
+    /// Picks the fork from the state's slot and `network`'s fork schedule.
+    pub fn from_ssz_bytes_for_network(bytes: &[u8], network: Network) -> Result<Self, DecodeError> {
+        let slot = peek_slot(FixtureKind::State, bytes).ok_or_else(|| {
+            DecodeError::BytesInvalid("state too short to contain a slot".to_string())
+        })?;
+        Self::from_ssz_bytes_for_fork(bytes, network.fork_at_slot(slot))
+    }
+
+    /// Picks the fork from an `Eth-Consensus-Version` header value.
+    pub fn from_ssz_bytes_with_consensus_version(
+        bytes: &[u8],
+        consensus_version: &str,
+    ) -> Result<Self, DecodeError> {
+        let fork = consensus_version
+            .parse()
+            .map_err(|e: crate::ArenaFetchError| DecodeError::BytesInvalid(e.to_string()))?;
+        Self::from_ssz_bytes_for_fork(bytes, fork)
+    }
+}
+
+impl<P: Preset> SszEncode for BeaconStateAnyFork<P> {
+    fn is_ssz_static() -> bool {
+        false
+    }
+
+    fn ssz_fixed_len() -> usize {
+        BYTES_PER_LENGTH_OFFSET
+    }
+
+    fn ssz_max_len() -> usize {
+        [
+            <BeaconStatePhase0<P> as SszEncode>::ssz_max_len(),
+            <BeaconStateAltair<P> as SszEncode>::ssz_max_len(),
+            <BeaconStateBellatrix<P> as SszEncode>::ssz_max_len(),
+            <BeaconStateCapella<P> as SszEncode>::ssz_max_len(),
+            <BeaconStateDeneb<P> as SszEncode>::ssz_max_len(),
+            <BeaconStateElectra<P> as SszEncode>::ssz_max_len(),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        match self {
+            Self::Phase0(state) => state.ssz_bytes_len(),
+            Self::Altair(state) => state.ssz_bytes_len(),
+            Self::Bellatrix(state) => state.ssz_bytes_len(),
+            Self::Capella(state) => state.ssz_bytes_len(),
+            Self::Deneb(state) => state.ssz_bytes_len(),
+            Self::Electra(state) => state.ssz_bytes_len(),
+        }
+    }
+
+    fn ssz_write_fixed(&self, offset: &mut usize, buf: &mut impl BufMut) {
+        buf.put_slice(&offset.to_le_bytes()[0..BYTES_PER_LENGTH_OFFSET]);
+        *offset += self.ssz_bytes_len();
+    }
+
+    fn ssz_write_variable(&self, buf: &mut impl BufMut) {
+        self.ssz_write(buf);
+    }
+
+    fn ssz_write(&self, buf: &mut impl BufMut) {
+        match self {
+            Self::Phase0(state) => state.ssz_write(buf),
+            Self::Altair(state) => state.ssz_write(buf),
+            Self::Bellatrix(state) => state.ssz_write(buf),
+            Self::Capella(state) => state.ssz_write(buf),
+            Self::Deneb(state) => state.ssz_write(buf),
+            Self::Electra(state) => state.ssz_write(buf),
+        }
+    }
+}
+
+impl<P: Preset> TreeHash for BeaconStateAnyFork<P> {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::Container
+    }
+
+    fn tree_hash_packed_encoding(&self) -> PackedEncoding {
+        unreachable!("Container should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("Container should never be packed.")
+    }
+
+    fn tree_hash_root(&self) -> Hash256 {
+        match self {
+            Self::Phase0(state) => state.tree_hash_root(),
+            Self::Altair(state) => state.tree_hash_root(),
+            Self::Bellatrix(state) => state.tree_hash_root(),
+            Self::Capella(state) => state.tree_hash_root(),
+            Self::Deneb(state) => state.tree_hash_root(),
+            Self::Electra(state) => state.tree_hash_root(),
+        }
+    }
+}
@@ -1,4 +1,19 @@
+use alloy_primitives::B256;
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{ArenaFetchError, ForkName, Network};
+
+pub const DEFAULT_CHECKPOINTZ_URL: &str = "https://sync-mainnet.beaconcha.in";
+/// Default REST port of every consensus client, used for networks without public checkpointz.
+pub const LOCAL_BEACON_URL: &str = "http://localhost:5052";
+const SSZ_CONTENT_TYPE: &str = "application/octet-stream";
+const CONSENSUS_VERSION: &str = "Eth-Consensus-Version";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
 
 #[derive(Deserialize, Debug)]
 struct CheckpointzSlotsResponse {
@@ -15,43 +30,413 @@ struct CheckpointzSlot {
     slot: String,
 }
 
-pub fn get_latest_served_checkpoint_slot() -> Result<String, reqwest::Error> {
-    // response body has structure:
-    // data: { slots: [ { slot, ... }, ...] }
-    // we want the number at slot, so we want the laziest way of accessing body.data.slots[0].slot
-    let body = reqwest::blocking::get(format!(
-        "https://sync-mainnet.beaconcha.in/checkpointz/v1/beacon/slots"
-    ))?
-    .json::<CheckpointzSlotsResponse>()?;
-
-    let slot = body.data.slots.first().unwrap().slot.clone();
-    Ok(slot)
-}
-
-pub fn get_block_bytes() -> Result<Vec<u8>, reqwest::Error> {
-    let slot = get_latest_served_checkpoint_slot()?;
-    let bytes = reqwest::blocking::Client::new()
-        .get(format!(
-            "https://sync-mainnet.beaconcha.in/eth/v2/beacon/blocks/{}",
-            slot
-        ))
-        .header("Accept", "application/octet-stream")
-        .send()?
-        .bytes()?;
-
-    Ok(bytes.to_vec())
-}
-
-pub fn get_state_bytes() -> Result<Vec<u8>, reqwest::Error> {
-    let slot = get_latest_served_checkpoint_slot()?;
-    let bytes = reqwest::blocking::Client::new()
-        .get(format!(
-            "https://sync-mainnet.beaconcha.in/eth/v2/debug/beacon/states/{}",
-            slot
-        ))
-        .header("Accept", "application/octet-stream")
-        .send()?
-        .bytes()?;
-
-    Ok(bytes.to_vec())
+#[derive(Deserialize, Debug)]
+struct HeaderResponse {
+    data: HeaderData,
+}
+
+#[derive(Deserialize, Debug)]
+struct HeaderData {
+    header: SignedHeader,
+}
+
+#[derive(Deserialize, Debug)]
+struct SignedHeader {
+    message: HeaderMessage,
+}
+
+#[derive(Deserialize, Debug)]
+struct HeaderMessage {
+    slot: String,
+}
+
+/// Block identifier as accepted by `/eth/v2/beacon/blocks/{block_id}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockId {
+    Head,
+    Genesis,
+    Finalized,
+    Slot(u64),
+    Root(B256),
+}
+
+/// State identifier as accepted by `/eth/v2/debug/beacon/states/{state_id}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateId {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+    Slot(u64),
+    /// The state root, not the root of the block that produced it.
+    Root(B256),
+}
+
+impl BlockId {
+    /// Whether the id resolves to a different block as the chain advances.
+    pub fn is_moving(&self) -> bool {
+        matches!(self, Self::Head | Self::Finalized)
+    }
+}
+
+impl StateId {
+    /// Whether the id resolves to a different state as the chain advances.
+    pub fn is_moving(&self) -> bool {
+        matches!(self, Self::Head | Self::Finalized | Self::Justified)
+    }
+}
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Head => f.write_str("head"),
+            Self::Genesis => f.write_str("genesis"),
+            Self::Finalized => f.write_str("finalized"),
+            Self::Slot(slot) => write!(f, "{slot}"),
+            Self::Root(root) => f.write_str(&alloy_primitives::hex::encode_prefixed(root)),
+        }
+    }
+}
+
+impl fmt::Display for StateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Head => f.write_str("head"),
+            Self::Genesis => f.write_str("genesis"),
+            Self::Finalized => f.write_str("finalized"),
+            Self::Justified => f.write_str("justified"),
+            Self::Slot(slot) => write!(f, "{slot}"),
+            Self::Root(root) => f.write_str(&alloy_primitives::hex::encode_prefixed(root)),
+        }
+    }
+}
+
+fn parse_slot_or_root(what: &str, s: &str) -> Result<Result<u64, B256>, ArenaFetchError> {
+    if let Ok(slot) = s.parse::<u64>() {
+        return Ok(Ok(slot));
+    }
+    if s.starts_with("0x") {
+        if let Ok(root) = B256::from_str(s) {
+            return Ok(Err(root));
+        }
+    }
+    Err(ArenaFetchError::Decode {
+        what: what.to_string(),
+        reason: format!("{s:?} is not a slot, 0x-prefixed root or named id"),
+    })
+}
+
+impl FromStr for BlockId {
+    type Err = ArenaFetchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(Self::Head),
+            "genesis" => Ok(Self::Genesis),
+            "finalized" => Ok(Self::Finalized),
+            _ => Ok(match parse_slot_or_root("block id", s)? {
+                Ok(slot) => Self::Slot(slot),
+                Err(root) => Self::Root(root),
+            }),
+        }
+    }
+}
+
+impl FromStr for StateId {
+    type Err = ArenaFetchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(Self::Head),
+            "genesis" => Ok(Self::Genesis),
+            "finalized" => Ok(Self::Finalized),
+            "justified" => Ok(Self::Justified),
+            _ => Ok(match parse_slot_or_root("state id", s)? {
+                Ok(slot) => Self::Slot(slot),
+                Err(root) => Self::Root(root),
+            }),
+        }
+    }
+}
+
+fn parse_served_slot(slot: &str, path: &str) -> Result<u64, ArenaFetchError> {
+    slot.parse().map_err(|_| ArenaFetchError::Decode {
+        what: format!("slot served at {path}"),
+        reason: format!("{slot:?} is not a number"),
+    })
+}
+
+/// SSZ bytes of a fetched object and the fork the server says they belong to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fetched {
+    pub bytes: Vec<u8>,
+    /// From the `Eth-Consensus-Version` header, if the server sent a known fork.
+    pub fork: Option<ForkName>,
+}
+
+/// Anything the arena can pull blocks and states from.
+pub trait BeaconSource {
+    /// Slot the arena should benchmark when none is pinned.
+    fn latest_slot(&self) -> Result<u64, ArenaFetchError>;
+    fn fetch_block(&self, block_id: &BlockId) -> Result<Fetched, ArenaFetchError>;
+    fn fetch_state(&self, state_id: &StateId) -> Result<Fetched, ArenaFetchError>;
+
+    fn block_bytes(&self, block_id: &BlockId) -> Result<Vec<u8>, ArenaFetchError> {
+        self.fetch_block(block_id).map(|fetched| fetched.bytes)
+    }
+
+    fn state_bytes(&self, state_id: &StateId) -> Result<Vec<u8>, ArenaFetchError> {
+        self.fetch_state(state_id).map(|fetched| fetched.bytes)
+    }
+}
+
+/// Client for the standard Beacon API served by any beacon node.
+#[derive(Clone, Debug)]
+pub struct BeaconApiClient {
+    base_url: String,
+    timeout: Duration,
+    accept: String,
+}
+
+impl BeaconApiClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            accept: SSZ_CONTENT_TYPE.to_string(),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_accept(mut self, accept: impl Into<String>) -> Self {
+        self.accept = accept.into();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn send(
+        &self,
+        url: &str,
+        accept: &str,
+    ) -> Result<reqwest::blocking::Response, ArenaFetchError> {
+        let response = reqwest::blocking::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .and_then(|client| client.get(url).header("Accept", accept).send())
+            .map_err(|e| ArenaFetchError::transport(url, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ArenaFetchError::Status {
+                url: url.to_string(),
+                status: status.as_u16(),
+            });
+        }
+        Ok(response)
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, ArenaFetchError> {
+        let url = format!("{}{}", self.base_url, path);
+        self.send(&url, "application/json")?
+            .json::<T>()
+            .map_err(|e| ArenaFetchError::Decode {
+                what: format!("JSON from {url}"),
+                reason: e.to_string(),
+            })
+    }
+
+    fn get_ssz(&self, path: &str) -> Result<Fetched, ArenaFetchError> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.send(&url, &self.accept)?;
+
+        // only SSZ is checked, a caller asking for JSON gets whatever the node sends
+        if self.accept == SSZ_CONTENT_TYPE {
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default();
+            if !content_type.starts_with(SSZ_CONTENT_TYPE) {
+                return Err(ArenaFetchError::ContentType {
+                    url,
+                    content_type: content_type.to_string(),
+                });
+            }
+        }
+        let fork = response
+            .headers()
+            .get(CONSENSUS_VERSION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        let expected = response
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+
+        let bytes = response
+            .bytes()
+            .map_err(|e| ArenaFetchError::transport(&url, e))?;
+
+        match expected {
+            Some(expected) if expected != bytes.len() as u64 => Err(ArenaFetchError::Truncated {
+                url,
+                expected,
+                actual: bytes.len(),
+            }),
+            _ => Ok(Fetched {
+                bytes: bytes.to_vec(),
+                fork,
+            }),
+        }
+    }
+
+    pub fn finalized_slot(&self) -> Result<u64, ArenaFetchError> {
+        let path = "/eth/v1/beacon/headers/finalized";
+        let body: HeaderResponse = self.get_json(path)?;
+        parse_served_slot(&body.data.header.message.slot, path)
+    }
+}
+
+impl BeaconSource for BeaconApiClient {
+    fn latest_slot(&self) -> Result<u64, ArenaFetchError> {
+        self.finalized_slot()
+    }
+
+    fn fetch_block(&self, block_id: &BlockId) -> Result<Fetched, ArenaFetchError> {
+        self.get_ssz(&format!("/eth/v2/beacon/blocks/{block_id}"))
+    }
+
+    fn fetch_state(&self, state_id: &StateId) -> Result<Fetched, ArenaFetchError> {
+        self.get_ssz(&format!("/eth/v2/debug/beacon/states/{state_id}"))
+    }
+}
+
+/// Client for a checkpointz server, which proxies a subset of the Beacon API
+/// and only serves the slots listed under `/checkpointz/v1/beacon/slots`.
+#[derive(Clone, Debug)]
+pub struct CheckpointzClient {
+    api: BeaconApiClient,
+}
+
+impl Default for CheckpointzClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHECKPOINTZ_URL)
+    }
+}
+
+impl CheckpointzClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            api: BeaconApiClient::new(base_url),
+        }
+    }
+
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            api: self.api.with_timeout(timeout),
+        }
+    }
+
+    pub fn with_accept(self, accept: impl Into<String>) -> Self {
+        Self {
+            api: self.api.with_accept(accept),
+        }
+    }
+
+    pub fn api(&self) -> &BeaconApiClient {
+        &self.api
+    }
+
+    pub fn latest_served_slot(&self) -> Result<u64, ArenaFetchError> {
+        // response body has structure:
+        // data: { slots: [ { slot, ... }, ...] }
+        // we want the number at slot, so we want the laziest way of accessing body.data.slots[0].slot
+        let path = "/checkpointz/v1/beacon/slots";
+        let body: CheckpointzSlotsResponse = self.api.get_json(path)?;
+
+        let slot = body
+            .data
+            .slots
+            .first()
+            .ok_or_else(|| ArenaFetchError::NoSlotsServed {
+                url: format!("{}{}", self.api.base_url, path),
+            })?;
+        parse_served_slot(&slot.slot, path)
+    }
+}
+
+impl BeaconSource for CheckpointzClient {
+    fn latest_slot(&self) -> Result<u64, ArenaFetchError> {
+        self.latest_served_slot()
+    }
+
+    fn fetch_block(&self, block_id: &BlockId) -> Result<Fetched, ArenaFetchError> {
+        self.api.fetch_block(block_id)
+    }
+
+    fn fetch_state(&self, state_id: &StateId) -> Result<Fetched, ArenaFetchError> {
+        self.api.fetch_state(state_id)
+    }
+}
+
+/// Picks the source from the environment:
+/// `SSZ_ARENA_BEACON_URL` selects a plain beacon node, `SSZ_ARENA_CHECKPOINTZ_URL`
+/// a checkpointz server, and otherwise the public checkpointz of the
+/// `SSZ_ARENA_NETWORK` network is used, or a local node if it has none.
+/// `SSZ_ARENA_TIMEOUT_SECS` overrides the request timeout for any of them.
+pub fn source_from_env() -> Result<Box<dyn BeaconSource>, ArenaFetchError> {
+    Ok(source_for_network(Network::from_env()?))
+}
+
+/// Like [`source_from_env`], but defaulting to `network`'s checkpointz server
+/// rather than `SSZ_ARENA_NETWORK`'s.
+pub fn source_for_network(network: Network) -> Box<dyn BeaconSource> {
+    let timeout = std::env::var("SSZ_ARENA_TIMEOUT_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TIMEOUT);
+
+    if let Ok(url) = std::env::var("SSZ_ARENA_BEACON_URL") {
+        return Box::new(BeaconApiClient::new(url).with_timeout(timeout));
+    }
+    if let Ok(url) = std::env::var("SSZ_ARENA_CHECKPOINTZ_URL") {
+        return Box::new(CheckpointzClient::new(url).with_timeout(timeout));
+    }
+    match network.checkpointz_url() {
+        Some(url) => Box::new(CheckpointzClient::new(url).with_timeout(timeout)),
+        None => Box::new(BeaconApiClient::new(LOCAL_BEACON_URL).with_timeout(timeout)),
+    }
+}
+
+pub fn get_latest_served_checkpoint_slot() -> Result<u64, ArenaFetchError> {
+    source_from_env()?.latest_slot()
+}
+
+pub fn get_block_bytes() -> Result<Vec<u8>, ArenaFetchError> {
+    let source = source_from_env()?;
+    let slot = source.latest_slot()?;
+    source.block_bytes(&BlockId::Slot(slot))
+}
+
+pub fn get_state_bytes() -> Result<Vec<u8>, ArenaFetchError> {
+    let source = source_from_env()?;
+    let slot = source.latest_slot()?;
+    source.state_bytes(&StateId::Slot(slot))
+}
+
+pub fn get_block_bytes_at(block_id: BlockId) -> Result<Vec<u8>, ArenaFetchError> {
+    source_from_env()?.block_bytes(&block_id)
+}
+
+pub fn get_state_bytes_at(state_id: StateId) -> Result<Vec<u8>, ArenaFetchError> {
+    source_from_env()?.state_bytes(&state_id)
 }
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{format_ns, BenchRecord};
+
+/// Regressions smaller than this are ignored unless `--threshold` says otherwise.
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 5.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Slower by more than the threshold, and criterion's confidence intervals don't overlap.
+    Regressed,
+    Improved,
+    /// Moved by more than the threshold, but within criterion's confidence intervals.
+    Noise,
+    Unchanged,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Regressed => "regressed",
+            Self::Improved => "improved",
+            Self::Noise => "noise",
+            Self::Unchanged => "unchanged",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub source: String,
+    pub id: String,
+    pub base_ns: f64,
+    pub new_ns: f64,
+    /// `new / base - 1`, positive when slower.
+    pub change: f64,
+    pub verdict: Verdict,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CompareReport {
+    pub comparisons: Vec<Comparison>,
+    /// Benchmarks only one of the runs has.
+    pub only_in_base: Vec<String>,
+    pub only_in_new: Vec<String>,
+    /// Benchmarks whose input fingerprint differs, so their times aren't comparable.
+    pub input_changed: Vec<String>,
+}
+
+impl CompareReport {
+    pub fn regressions(&self) -> impl Iterator<Item = &Comparison> {
+        self.comparisons
+            .iter()
+            .filter(|comparison| comparison.verdict == Verdict::Regressed)
+    }
+}
+
+fn key(record: &BenchRecord) -> (String, String) {
+    (record.source.clone(), record.id.clone())
+}
+
+/// Whether the 95% confidence intervals of the two means are disjoint. Records
+/// without intervals, i.e. divan's, only have the threshold to go by.
+fn significant(base: &BenchRecord, new: &BenchRecord) -> bool {
+    match (
+        base.mean_lower_ns,
+        base.mean_upper_ns,
+        new.mean_lower_ns,
+        new.mean_upper_ns,
+    ) {
+        (Some(base_lower), Some(base_upper), Some(new_lower), Some(new_upper)) => {
+            new_lower > base_upper || new_upper < base_lower
+        }
+        _ => true,
+    }
+}
+
+/// Matches `base` and `new` by (source, benchmark id) and classifies every
+/// change beyond `threshold_percent`.
+pub fn compare(base: &[BenchRecord], new: &[BenchRecord], threshold_percent: f64) -> CompareReport {
+    let threshold = threshold_percent / 100.0;
+    let base: BTreeMap<_, _> = base.iter().map(|record| (key(record), record)).collect();
+    let new: BTreeMap<_, _> = new.iter().map(|record| (key(record), record)).collect();
+    let name = |(source, id): &(String, String)| format!("{source}:{id}");
+
+    let mut report = CompareReport::default();
+    for (key, base) in &base {
+        let Some(new) = new.get(key) else {
+            report.only_in_base.push(name(key));
+            continue;
+        };
+        if base.input != new.input {
+            report.input_changed.push(name(key));
+            continue;
+        }
+        let change = new.mean_ns / base.mean_ns - 1.0;
+        let verdict = if change.abs() <= threshold {
+            Verdict::Unchanged
+        } else if !significant(base, new) {
+            Verdict::Noise
+        } else if change > 0.0 {
+            Verdict::Regressed
+        } else {
+            Verdict::Improved
+        };
+        report.comparisons.push(Comparison {
+            source: key.0.clone(),
+            id: key.1.clone(),
+            base_ns: base.mean_ns,
+            new_ns: new.mean_ns,
+            change,
+            verdict,
+        });
+    }
+    report.only_in_new = new
+        .keys()
+        .filter(|key| !base.contains_key(*key))
+        .map(name)
+        .collect();
+    report
+}
+
+impl fmt::Display for CompareReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .comparisons
+            .iter()
+            .map(|c| c.source.len() + c.id.len() + 1)
+            .max()
+            .unwrap_or_default();
+        for c in &self.comparisons {
+            writeln!(
+                f,
+                "{:<width$}  {:>10} -> {:<10} {:>+8.2}%  {}",
+                format!("{}:{}", c.source, c.id),
+                format_ns(c.base_ns),
+                format_ns(c.new_ns),
+                c.change * 100.0,
+                c.verdict
+            )?;
+        }
+        for (what, ids) in [
+            ("input changed, skipped", &self.input_changed),
+            ("only in base", &self.only_in_base),
+            ("only in new", &self.only_in_new),
+        ] {
+            for id in ids {
+                writeln!(f, "{id}: {what}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, mean_ns: f64, interval: Option<(f64, f64)>) -> BenchRecord {
+        // only criterion measures confidence intervals
+        let source = match interval {
+            Some(_) => "criterion",
+            None => "divan",
+        };
+        BenchRecord {
+            source: source.to_string(),
+            library: "Sszb".to_string(),
+            object: "SignedBeaconBlock".to_string(),
+            operation: "decode".to_string(),
+            input: "mainnet/block/00".to_string(),
+            git_rev: "0123456789ab".to_string(),
+            sszb_rev: None,
+            id: id.to_string(),
+            mean_ns,
+            mean_lower_ns: interval.map(|(lower, _)| lower),
+            mean_upper_ns: interval.map(|(_, upper)| upper),
+            median_ns: mean_ns,
+            stddev_ns: None,
+            samples: None,
+            throughput_bytes: None,
+            bytes_per_sec: None,
+            alloc_count: None,
+            alloc_bytes: None,
+        }
+    }
+
+    fn verdict(base: BenchRecord, new: BenchRecord) -> Verdict {
+        let report = compare(&[base], &[new], DEFAULT_THRESHOLD_PERCENT);
+        assert_eq!(report.comparisons.len(), 1);
+        report.comparisons[0].verdict
+    }
+
+    #[test]
+    fn regression_with_disjoint_intervals() {
+        let base = record("block/decode", 100.0, Some((98.0, 102.0)));
+        let new = record("block/decode", 110.0, Some((108.0, 112.0)));
+        let report = compare(&[base], &[new], DEFAULT_THRESHOLD_PERCENT);
+        assert_eq!(report.regressions().count(), 1);
+        let comparison = &report.comparisons[0];
+        assert_eq!(comparison.verdict, Verdict::Regressed);
+        assert!((comparison.change - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn overlapping_intervals_are_noise() {
+        let base = record("block/decode", 100.0, Some((90.0, 115.0)));
+        let new = record("block/decode", 110.0, Some((100.0, 120.0)));
+        assert_eq!(verdict(base, new), Verdict::Noise);
+    }
+
+    #[test]
+    fn improvement_and_unchanged() {
+        let base = record("block/decode", 100.0, Some((98.0, 102.0)));
+        let new = record("block/decode", 80.0, Some((78.0, 82.0)));
+        assert_eq!(verdict(base.clone(), new), Verdict::Improved);
+        let new = record("block/decode", 103.0, Some((101.0, 105.0)));
+        assert_eq!(verdict(base, new), Verdict::Unchanged);
+    }
+
+    #[test]
+    fn divan_records_only_need_the_threshold() {
+        let base = record("sszb::decode_beacon_block", 100.0, None);
+        let new = record("sszb::decode_beacon_block", 110.0, None);
+        assert_eq!(verdict(base.clone(), new), Verdict::Regressed);
+        let new = record("sszb::decode_beacon_block", 104.0, None);
+        assert_eq!(verdict(base, new), Verdict::Unchanged);
+    }
+
+    #[test]
+    fn changed_inputs_and_missing_benchmarks_are_skipped() {
+        let base = [
+            record("block/decode", 100.0, Some((98.0, 102.0))),
+            record("block/encode", 100.0, Some((98.0, 102.0))),
+        ];
+        let new = [
+            BenchRecord {
+                input: "mainnet/block/ff".to_string(),
+                ..record("block/decode", 200.0, Some((198.0, 202.0)))
+            },
+            record("block/hash_tree_root", 100.0, Some((98.0, 102.0))),
+        ];
+        let report = compare(&base, &new, DEFAULT_THRESHOLD_PERCENT);
+        assert!(report.comparisons.is_empty());
+        assert_eq!(report.input_changed, ["criterion:block/decode"]);
+        assert_eq!(report.only_in_base, ["criterion:block/encode"]);
+        assert_eq!(report.only_in_new, ["criterion:block/hash_tree_root"]);
+    }
+}
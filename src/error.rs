@@ -0,0 +1,98 @@
+use std::fmt;
+
+/// Everything that can go wrong while getting a benchmark input onto disk and into memory.
+#[derive(Debug)]
+pub enum ArenaFetchError {
+    /// The checkpointz server answered but lists no slots.
+    NoSlotsServed {
+        url: String,
+    },
+    /// The request or body transfer failed: DNS, TLS, timeout, connection reset.
+    Transport {
+        url: String,
+        reason: String,
+    },
+    /// The server answered with a non-2xx status.
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// The server answered with something other than SSZ bytes.
+    ContentType {
+        url: String,
+        content_type: String,
+    },
+    /// The body length doesn't match the advertised `Content-Length`.
+    Truncated {
+        url: String,
+        expected: u64,
+        actual: usize,
+    },
+    /// A response or fixture could not be decoded.
+    Decode {
+        what: String,
+        reason: String,
+    },
+    Io(std::io::Error),
+    /// Offline mode was requested but the cache has no entry for this key.
+    MissingFixture(String),
+    /// A cached blob no longer hashes to the digest it is stored under.
+    ChecksumMismatch {
+        expected: String,
+        actual: String,
+    },
+}
+
+impl ArenaFetchError {
+    pub(crate) fn transport(url: &str, e: reqwest::Error) -> Self {
+        Self::Transport {
+            url: url.to_string(),
+            reason: e.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ArenaFetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSlotsServed { url } => write!(f, "{url} serves no checkpoint slots"),
+            Self::Transport { url, reason } => write!(f, "request to {url} failed: {reason}"),
+            Self::Status { url, status } => write!(f, "{url} returned HTTP {status}"),
+            Self::ContentType { url, content_type } => {
+                write!(f, "{url} returned {content_type:?} instead of SSZ")
+            }
+            Self::Truncated {
+                url,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{url} returned {actual} bytes, expected {expected} from Content-Length"
+            ),
+            Self::Decode { what, reason } => write!(f, "failed to decode {what}: {reason}"),
+            Self::Io(e) => write!(f, "fixture cache io error: {e}"),
+            Self::MissingFixture(key) => write!(
+                f,
+                "no cached fixture for {key}, run `cargo run --release -- fetch` with network access first"
+            ),
+            Self::ChecksumMismatch { expected, actual } => {
+                write!(f, "fixture checksum mismatch: expected {expected}, got {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArenaFetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ArenaFetchError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
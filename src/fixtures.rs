@@ -0,0 +1,507 @@
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::checkpointz::{source_for_network, BeaconSource, BlockId, StateId};
+use crate::{ArenaFetchError, Network};
+
+const DEFAULT_FIXTURES_DIR: &str = "target/ssz-fixtures";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixtureKind {
+    Block,
+    State,
+}
+
+impl FixtureKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::State => "state",
+        }
+    }
+
+    /// File in the repo root that overrides both the cache and the network.
+    fn override_path(&self) -> &'static str {
+        match self {
+            Self::Block => "beacon-block.ssz",
+            Self::State => "beacon-state.ssz",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Serve from the cache when possible, otherwise fetch and store.
+    Online,
+    /// Never touch the network, fail if the fixture isn't cached.
+    Offline,
+}
+
+impl FixtureMode {
+    /// `SSZ_ARENA_OFFLINE=1` switches every loader to offline mode.
+    pub fn from_env() -> Self {
+        match std::env::var("SSZ_ARENA_OFFLINE").as_deref() {
+            Ok("1") | Ok("true") => Self::Offline,
+            _ => Self::Online,
+        }
+    }
+}
+
+/// Content-addressed store of fetched SSZ objects.
+///
+/// Blobs live under `blobs/<sha256>.ssz` and are referenced from
+/// `<network>/<kind>/<id>` index files holding the digest, where the id is a
+/// slot, root or `genesis`. The `latest` file next to them pins the id that
+/// offline runs should use.
+pub struct FixtureCache {
+    root: PathBuf,
+    network: Network,
+}
+
+impl FixtureCache {
+    pub fn new(root: impl Into<PathBuf>, network: Network) -> Self {
+        Self {
+            root: root.into(),
+            network,
+        }
+    }
+
+    /// Cache for `SSZ_ARENA_NETWORK`, rooted at `SSZ_ARENA_FIXTURES_DIR` or `target/ssz-fixtures`.
+    pub fn from_env() -> Result<Self, ArenaFetchError> {
+        Ok(Self::for_network(Network::from_env()?))
+    }
+
+    /// Like [`FixtureCache::from_env`], but for `network` rather than `SSZ_ARENA_NETWORK`.
+    pub fn for_network(network: Network) -> Self {
+        let root = std::env::var("SSZ_ARENA_FIXTURES_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_FIXTURES_DIR));
+        Self::new(root, network)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    fn index_dir(&self, kind: FixtureKind) -> PathBuf {
+        self.root.join(self.network.as_str()).join(kind.as_str())
+    }
+
+    fn missing(&self, kind: FixtureKind, key: &str) -> ArenaFetchError {
+        ArenaFetchError::MissingFixture(format!("{}/{}/{key}", self.network, kind.as_str()))
+    }
+
+    fn blob_path(&self, digest: &str) -> PathBuf {
+        self.root.join("blobs").join(format!("{digest}.ssz"))
+    }
+
+    /// Digest `key` points at, without reading the blob.
+    pub fn digest(&self, kind: FixtureKind, key: &str) -> Result<Option<String>, ArenaFetchError> {
+        match std::fs::read_to_string(self.index_dir(kind).join(key)) {
+            Ok(digest) => Ok(Some(digest.trim().to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn get(&self, kind: FixtureKind, key: &str) -> Result<Option<Vec<u8>>, ArenaFetchError> {
+        let Some(digest) = self.digest(kind, key)? else {
+            return Ok(None);
+        };
+        let bytes = std::fs::read(self.blob_path(&digest))?;
+        let actual = sha256_hex(&bytes);
+        if actual != digest {
+            return Err(ArenaFetchError::ChecksumMismatch {
+                expected: digest,
+                actual,
+            });
+        }
+        Ok(Some(bytes))
+    }
+
+    /// Stores `bytes` under its digest and points `key` at it.
+    pub fn put(
+        &self,
+        kind: FixtureKind,
+        key: &str,
+        bytes: &[u8],
+    ) -> Result<String, ArenaFetchError> {
+        let digest = sha256_hex(bytes);
+        let blob = self.blob_path(&digest);
+        if !blob.exists() {
+            std::fs::create_dir_all(blob.parent().expect("blob path has a parent"))?;
+            // write then rename so an interrupted fetch never leaves a truncated blob behind
+            let tmp = blob.with_extension("ssz.partial");
+            std::fs::write(&tmp, bytes)?;
+            std::fs::rename(&tmp, &blob)?;
+        }
+        let index_dir = self.index_dir(kind);
+        std::fs::create_dir_all(&index_dir)?;
+        std::fs::write(index_dir.join(key), &digest)?;
+        Ok(digest)
+    }
+
+    pub fn pinned_key(&self, kind: FixtureKind) -> Result<Option<String>, ArenaFetchError> {
+        match std::fs::read_to_string(self.index_dir(kind).join("latest")) {
+            Ok(key) => Ok(Some(key.trim().to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn pin(&self, kind: FixtureKind, key: &str) -> Result<(), ArenaFetchError> {
+        let index_dir = self.index_dir(kind);
+        std::fs::create_dir_all(&index_dir)?;
+        std::fs::write(index_dir.join("latest"), key)?;
+        Ok(())
+    }
+
+    fn load_pinned(&self, kind: FixtureKind) -> Result<Vec<u8>, ArenaFetchError> {
+        let key = self
+            .pinned_key(kind)?
+            .ok_or_else(|| self.missing(kind, "latest"))?;
+        self.get(kind, &key)?
+            .ok_or_else(|| self.missing(kind, &key))
+    }
+
+    /// Loads the fixture for `kind`, fetching it from the cache network's
+    /// [`BeaconSource`] at most once, see [`source_for_network`].
+    pub fn load(&self, kind: FixtureKind, mode: FixtureMode) -> Result<Vec<u8>, ArenaFetchError> {
+        self.load_from(&*source_for_network(self.network), kind, mode)
+    }
+
+    /// Object selection: `SSZ_ARENA_BLOCK_ID`/`SSZ_ARENA_STATE_ID` if set, then
+    /// `SSZ_ARENA_SLOT` for both, else the pinned object in offline mode and
+    /// the source's latest slot online.
+    pub fn load_from(
+        &self,
+        source: &dyn BeaconSource,
+        kind: FixtureKind,
+        mode: FixtureMode,
+    ) -> Result<Vec<u8>, ArenaFetchError> {
+        let id = match env_id(kind) {
+            Some(id) => id,
+            None if mode == FixtureMode::Offline => return self.load_pinned(kind),
+            None => source.latest_slot()?.to_string(),
+        };
+        match kind {
+            FixtureKind::Block => self.load_block_at(source, id.parse()?, mode),
+            FixtureKind::State => self.load_state_at(source, id.parse()?, mode),
+        }
+    }
+
+    pub fn load_block_at(
+        &self,
+        source: &dyn BeaconSource,
+        block_id: BlockId,
+        mode: FixtureMode,
+    ) -> Result<Vec<u8>, ArenaFetchError> {
+        self.load_object(
+            FixtureKind::Block,
+            &block_id.to_string(),
+            block_id.is_moving(),
+            mode,
+            || source.block_bytes(&block_id),
+        )
+    }
+
+    pub fn load_state_at(
+        &self,
+        source: &dyn BeaconSource,
+        state_id: StateId,
+        mode: FixtureMode,
+    ) -> Result<Vec<u8>, ArenaFetchError> {
+        self.load_object(
+            FixtureKind::State,
+            &state_id.to_string(),
+            state_id.is_moving(),
+            mode,
+            || source.state_bytes(&state_id),
+        )
+    }
+
+    /// Moving ids like `head` can't be cache keys, so whatever they resolve
+    /// to is stored under its slot and offline runs fall back to the pin.
+    fn load_object(
+        &self,
+        kind: FixtureKind,
+        key: &str,
+        moving: bool,
+        mode: FixtureMode,
+        fetch: impl FnOnce() -> Result<Vec<u8>, ArenaFetchError>,
+    ) -> Result<Vec<u8>, ArenaFetchError> {
+        if moving {
+            if mode == FixtureMode::Offline {
+                return self.load_pinned(kind);
+            }
+            let bytes = fetch()?;
+            let slot = peek_slot(kind, &bytes).ok_or_else(|| ArenaFetchError::Decode {
+                what: format!("{} for {key}", kind.as_str()),
+                reason: "too short to contain a slot".to_string(),
+            })?;
+            self.put(kind, &slot.to_string(), &bytes)?;
+            self.pin(kind, &slot.to_string())?;
+            return Ok(bytes);
+        }
+
+        if let Some(bytes) = self.get(kind, key)? {
+            self.pin(kind, key)?;
+            return Ok(bytes);
+        }
+        if mode == FixtureMode::Offline {
+            return Err(self.missing(kind, key));
+        }
+
+        let bytes = fetch()?;
+        self.put(kind, key, &bytes)?;
+        self.pin(kind, key)?;
+        Ok(bytes)
+    }
+}
+
+/// Reads the slot straight out of the SSZ bytes without decoding the object.
+pub fn peek_slot(kind: FixtureKind, bytes: &[u8]) -> Option<u64> {
+    let start = match kind {
+        // SignedBeaconBlock starts with the offset of its variable-size `message`,
+        // whose first field is the slot
+        FixtureKind::Block => u32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?) as usize,
+        // BeaconState: genesis_time (8) and genesis_validators_root (32) precede the slot
+        FixtureKind::State => 40,
+    };
+    let slot = bytes.get(start..start.checked_add(8)?)?;
+    Some(u64::from_le_bytes(slot.try_into().ok()?))
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    alloy_primitives::hex::encode(Sha256::digest(bytes))
+}
+
+fn env_id(kind: FixtureKind) -> Option<String> {
+    let var = match kind {
+        FixtureKind::Block => "SSZ_ARENA_BLOCK_ID",
+        FixtureKind::State => "SSZ_ARENA_STATE_ID",
+    };
+    std::env::var(var)
+        .or_else(|_| std::env::var("SSZ_ARENA_SLOT"))
+        .ok()
+}
+
+/// Cache key of the id from the environment in the canonical form
+/// [`FixtureCache::load_from`] stores it under, i.e. roots in lower case and
+/// slots without leading zeros, and whether the id moves.
+fn env_key(kind: FixtureKind) -> Result<Option<(String, bool)>, ArenaFetchError> {
+    let Some(id) = env_id(kind) else {
+        return Ok(None);
+    };
+    Ok(Some(match kind {
+        FixtureKind::Block => {
+            let id: BlockId = id.parse()?;
+            (id.to_string(), id.is_moving())
+        }
+        FixtureKind::State => {
+            let id: StateId = id.parse()?;
+            (id.to_string(), id.is_moving())
+        }
+    }))
+}
+
+/// Digest of the fixture the benches would load for `kind` on `network`,
+/// found without touching the network: the override file if present, else
+/// the cache entry for the id from the environment or the pinned one.
+pub fn fixture_digest(
+    kind: FixtureKind,
+    network: Network,
+) -> Result<Option<String>, ArenaFetchError> {
+    if let Ok(bytes) = std::fs::read(kind.override_path()) {
+        return Ok(Some(sha256_hex(&bytes)));
+    }
+    let cache = FixtureCache::for_network(network);
+    let key = match env_key(kind)? {
+        Some((key, false)) => key,
+        // moving ids are stored under the slot they resolved to, which the pin names
+        _ => match cache.pinned_key(kind)? {
+            Some(key) => key,
+            None => return Ok(None),
+        },
+    };
+    cache.digest(kind, &key)
+}
+
+fn load(kind: FixtureKind, network: Network) -> Result<Vec<u8>, ArenaFetchError> {
+    if let Ok(bytes) = std::fs::read(kind.override_path()) {
+        return Ok(bytes);
+    }
+    FixtureCache::for_network(network).load(kind, FixtureMode::from_env())
+}
+
+/// Bytes of the benchmark block: `beacon-block.ssz` if present, then the fixture cache.
+pub fn load_block_bytes() -> Result<Vec<u8>, ArenaFetchError> {
+    load(FixtureKind::Block, Network::from_env()?)
+}
+
+/// Bytes of the benchmark state: `beacon-state.ssz` if present, then the fixture cache.
+pub fn load_state_bytes() -> Result<Vec<u8>, ArenaFetchError> {
+    load(FixtureKind::State, Network::from_env()?)
+}
+
+/// Like [`load_block_bytes`], but for `network` rather than `SSZ_ARENA_NETWORK`,
+/// for benches whose decoders are fixed to one network.
+pub fn load_block_bytes_for(network: Network) -> Result<Vec<u8>, ArenaFetchError> {
+    load(FixtureKind::Block, network)
+}
+
+/// Like [`load_state_bytes`], but for `network` rather than `SSZ_ARENA_NETWORK`.
+pub fn load_state_bytes_for(network: Network) -> Result<Vec<u8>, ArenaFetchError> {
+    load(FixtureKind::State, network)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::checkpointz::Fetched;
+
+    /// A fresh cache directory under the system temp dir, removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("ssz-arena-fixtures-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Serves the same state for every id and counts the requests.
+    struct StubSource {
+        bytes: Vec<u8>,
+        requests: Cell<usize>,
+    }
+
+    impl StubSource {
+        fn new(slot: u64) -> Self {
+            Self {
+                bytes: state_bytes(slot),
+                requests: Cell::new(0),
+            }
+        }
+    }
+
+    impl BeaconSource for StubSource {
+        fn latest_slot(&self) -> Result<u64, ArenaFetchError> {
+            Ok(peek_slot(FixtureKind::State, &self.bytes).unwrap())
+        }
+
+        fn fetch_block(&self, _: &BlockId) -> Result<Fetched, ArenaFetchError> {
+            unreachable!("only states are fetched")
+        }
+
+        fn fetch_state(&self, _: &StateId) -> Result<Fetched, ArenaFetchError> {
+            self.requests.set(self.requests.get() + 1);
+            Ok(Fetched {
+                bytes: self.bytes.clone(),
+                fork: None,
+            })
+        }
+    }
+
+    /// Just enough of a state for [`peek_slot`].
+    fn state_bytes(slot: u64) -> Vec<u8> {
+        let mut bytes = vec![0xab; 64];
+        bytes[40..48].copy_from_slice(&slot.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn put_then_get_round_trips() {
+        let dir = TempDir::new("round-trip");
+        let cache = FixtureCache::new(&dir.0, Network::Mainnet);
+        let bytes = state_bytes(7);
+
+        let digest = cache.put(FixtureKind::State, "7", &bytes).unwrap();
+        assert_eq!(digest, sha256_hex(&bytes));
+        assert_eq!(cache.digest(FixtureKind::State, "7").unwrap(), Some(digest));
+        assert_eq!(cache.get(FixtureKind::State, "7").unwrap(), Some(bytes));
+        assert_eq!(cache.get(FixtureKind::State, "8").unwrap(), None);
+        assert_eq!(cache.get(FixtureKind::Block, "7").unwrap(), None);
+    }
+
+    #[test]
+    fn tampered_blob_fails_its_checksum() {
+        let dir = TempDir::new("tampered");
+        let cache = FixtureCache::new(&dir.0, Network::Mainnet);
+        let digest = cache.put(FixtureKind::State, "7", &state_bytes(7)).unwrap();
+        let tampered = state_bytes(8);
+        std::fs::write(cache.blob_path(&digest), &tampered).unwrap();
+
+        match cache.get(FixtureKind::State, "7") {
+            Err(ArenaFetchError::ChecksumMismatch { expected, actual }) => {
+                assert_eq!(expected, digest);
+                assert_eq!(actual, sha256_hex(&tampered));
+            }
+            other => panic!("expected a checksum mismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn offline_without_cache_or_pin_is_missing() {
+        let dir = TempDir::new("offline");
+        let cache = FixtureCache::new(&dir.0, Network::Mainnet);
+        let source = StubSource::new(7);
+
+        match cache.load_state_at(&source, StateId::Head, FixtureMode::Offline) {
+            Err(ArenaFetchError::MissingFixture(key)) => assert_eq!(key, "mainnet/state/latest"),
+            other => panic!("expected a missing fixture, got {other:?}"),
+        }
+        match cache.load_state_at(&source, StateId::Slot(7), FixtureMode::Offline) {
+            Err(ArenaFetchError::MissingFixture(key)) => assert_eq!(key, "mainnet/state/7"),
+            other => panic!("expected a missing fixture, got {other:?}"),
+        }
+        assert_eq!(source.requests.get(), 0);
+    }
+
+    #[test]
+    fn moving_id_is_stored_under_its_slot_and_pinned() {
+        let dir = TempDir::new("moving");
+        let cache = FixtureCache::new(&dir.0, Network::Mainnet);
+        let source = StubSource::new(123);
+
+        let bytes = cache
+            .load_state_at(&source, StateId::Head, FixtureMode::Online)
+            .unwrap();
+        assert_eq!(bytes, source.bytes);
+        assert_eq!(
+            cache.digest(FixtureKind::State, "123").unwrap(),
+            Some(sha256_hex(&bytes))
+        );
+        assert_eq!(cache.digest(FixtureKind::State, "head").unwrap(), None);
+        assert_eq!(
+            cache.pinned_key(FixtureKind::State).unwrap().as_deref(),
+            Some("123")
+        );
+
+        // offline runs fall back to the pin without touching the source
+        let offline = cache
+            .load_state_at(&source, StateId::Head, FixtureMode::Offline)
+            .unwrap();
+        assert_eq!(offline, bytes);
+        let cached = cache
+            .load_state_at(&source, StateId::Slot(123), FixtureMode::Offline)
+            .unwrap();
+        assert_eq!(cached, bytes);
+        assert_eq!(source.requests.get(), 1);
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ArenaFetchError, ForkEpochs, Network};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ForkName {
+    Phase0,
+    Altair,
+    Bellatrix,
+    Capella,
+    Deneb,
+    Electra,
+}
+
+impl ForkName {
+    pub const ALL: [ForkName; 6] = [
+        Self::Phase0,
+        Self::Altair,
+        Self::Bellatrix,
+        Self::Capella,
+        Self::Deneb,
+        Self::Electra,
+    ];
+
+    /// Name as used by the `Eth-Consensus-Version` header and the spec tests.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Phase0 => "phase0",
+            Self::Altair => "altair",
+            Self::Bellatrix => "bellatrix",
+            Self::Capella => "capella",
+            Self::Deneb => "deneb",
+            Self::Electra => "electra",
+        }
+    }
+}
+
+impl fmt::Display for ForkName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ForkName {
+    type Err = ArenaFetchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|fork| fork.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| ArenaFetchError::Decode {
+                what: "fork name".to_string(),
+                reason: format!("unknown fork {s:?}"),
+            })
+    }
+}
+
+impl From<ForkName> for sigp_types::ForkName {
+    fn from(fork: ForkName) -> Self {
+        match fork {
+            ForkName::Phase0 => Self::Base,
+            ForkName::Altair => Self::Altair,
+            ForkName::Bellatrix => Self::Bellatrix,
+            ForkName::Capella => Self::Capella,
+            ForkName::Deneb => Self::Deneb,
+            ForkName::Electra => Self::Electra,
+        }
+    }
+}
+
+impl ForkEpochs {
+    pub fn fork_at_epoch(&self, epoch: u64) -> ForkName {
+        match epoch {
+            e if e >= self.electra => ForkName::Electra,
+            e if e >= self.deneb => ForkName::Deneb,
+            e if e >= self.capella => ForkName::Capella,
+            e if e >= self.bellatrix => ForkName::Bellatrix,
+            e if e >= self.altair => ForkName::Altair,
+            _ => ForkName::Phase0,
+        }
+    }
+}
+
+impl Network {
+    pub fn fork_at_slot(&self, slot: u64) -> ForkName {
+        self.fork_epochs()
+            .fork_at_epoch(slot / self.slots_per_epoch())
+    }
+}
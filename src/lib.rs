@@ -1,14 +1,82 @@
+mod error;
+pub use error::ArenaFetchError;
+
+mod preset;
+pub use preset::{GnosisPreset, MainnetPreset, MinimalPreset, Preset};
+
+mod network;
+pub use network::{
+    ArenaSpec, ForkEpochs, ForkVersions, GnosisSpec, HoleskySpec, MainnetSpec, MinimalSpec,
+    Network, SepoliaSpec,
+};
+
+mod fork;
+pub use fork::ForkName;
+
 mod checkpointz;
-pub use checkpointz::{get_block_bytes, get_latest_served_checkpoint_slot, get_state_bytes};
+pub use checkpointz::{
+    get_block_bytes, get_block_bytes_at, get_latest_served_checkpoint_slot, get_state_bytes,
+    get_state_bytes_at, source_for_network, source_from_env, BeaconApiClient, BeaconSource,
+    BlockId, CheckpointzClient, Fetched, StateId, DEFAULT_CHECKPOINTZ_URL, LOCAL_BEACON_URL,
+};
+
+mod fixtures;
+pub use fixtures::{
+    fixture_digest, load_block_bytes, load_block_bytes_for, load_state_bytes, load_state_bytes_for,
+    peek_slot, sha256_hex, FixtureCache, FixtureKind, FixtureMode,
+};
+
+mod schema;
+mod verify;
+pub use verify::{verify_block, verify_state, Verified, VerifyError};
+
+mod malformed;
+pub use malformed::{check_rejected, malform, malformed_corpus, Mutation, RejectError};
+
+mod results;
+pub use results::{
+    collect_criterion, collect_divan, parse_divan, read_results, to_csv, write_results,
+    BenchRecord, ResultsError, Revisions, DEFAULT_CRITERION_DIR, DEFAULT_DIVAN_OUTPUT,
+    DEFAULT_RESULTS_DIR,
+};
+
+mod report;
+pub use report::{format_ns, render_html, render_markdown, render_report, ReportFormat};
+
+mod compare;
+pub use compare::{compare, CompareReport, Comparison, Verdict, DEFAULT_THRESHOLD_PERCENT};
+
+mod synthetic;
+pub use synthetic::{BlockGenerator, GenerateError, Participation, StateGenerator};
 
 mod mock_struct;
 pub use mock_struct::Foo;
 
 mod beacon_block;
-pub use beacon_block::SignedBeaconBlock;
+pub use beacon_block::{
+    Attestation, AttestationData, AttestationElectra, AttesterSlashing, AttesterSlashingElectra,
+    BeaconBlock, BeaconBlockAltair, BeaconBlockBellatrix, BeaconBlockBody, BeaconBlockBodyAltair,
+    BeaconBlockBodyBellatrix, BeaconBlockBodyCapella, BeaconBlockBodyElectra,
+    BeaconBlockBodyPhase0, BeaconBlockCapella, BeaconBlockElectra, BeaconBlockHeader,
+    BeaconBlockPhase0, BlsToExecutionChange, Checkpoint, ConsolidationRequest, Deposit,
+    DepositData, DepositRequest, Eth1Data, ExecutionPayload, ExecutionPayloadBellatrix,
+    ExecutionPayloadCapella, ExecutionRequests, IndexedAttestation, IndexedAttestationElectra,
+    ProposerSlashing, SignedBeaconBlock, SignedBeaconBlockAltair, SignedBeaconBlockAnyFork,
+    SignedBeaconBlockBellatrix, SignedBeaconBlockCapella, SignedBeaconBlockDeneb,
+    SignedBeaconBlockElectra, SignedBeaconBlockHeader, SignedBeaconBlockPhase0,
+    SignedBlsToExecutionChange, SignedVoluntaryExit, SyncAggregate, VoluntaryExit, Withdrawal,
+    WithdrawalRequest,
+};
 
 mod beacon_state;
-pub use beacon_state::{BeaconState, ExecutionPayloadHeader};
+// its Checkpoint, BeaconBlockHeader, Eth1Data and AttestationData are exported from beacon_block
+pub use beacon_state::{
+    BeaconState, BeaconStateAltair, BeaconStateAnyFork, BeaconStateBellatrix, BeaconStateCapella,
+    BeaconStateDeneb, BeaconStateElectra, BeaconStatePhase0, ExecutionPayloadHeader,
+    ExecutionPayloadHeaderBellatrix, ExecutionPayloadHeaderCapella, Fork, HistoricalSummary,
+    PendingAttestation, PendingConsolidation, PendingDeposit, PendingPartialWithdrawal,
+    SyncCommittee, Validator,
+};
 
 mod tx_opaque;
 pub use tx_opaque::*;
@@ -0,0 +1,366 @@
+use std::path::{Path, PathBuf};
+
+use ssz_arena::{
+    collect_criterion, collect_divan, compare, load_block_bytes, load_state_bytes,
+    malformed_corpus, peek_slot, read_results, render_report, sha256_hex, source_from_env,
+    with_arena_spec, write_results, ArenaFetchError, ArenaSpec, BeaconSource, BlockGenerator,
+    FixtureCache, FixtureKind, FixtureMode, ForkName, Network, ReportFormat, ResultsError,
+    Revisions, StateGenerator, StateId, DEFAULT_CRITERION_DIR, DEFAULT_DIVAN_OUTPUT,
+    DEFAULT_RESULTS_DIR, DEFAULT_THRESHOLD_PERCENT,
+};
+
+const DEFAULT_CORPUS_DIR: &str = "target/arena-corpus";
+
+const USAGE: &str = "usage:
+  ssz-arena fetch [block|state|all] [slot|root|head|finalized|genesis]
+  ssz-arena collect [--criterion <dir>] [--divan <file>] [--out <dir>]
+  ssz-arena report [--results <file>] [--baseline <library>] [--format markdown|html] [--out <file>]
+  ssz-arena compare <base results.json> <new results.json> [--threshold <percent>]
+  ssz-arena generate-state [--validators <n>] [--eth1-votes <n>] [--historical-summaries <n>]
+                           [--participation full|none|<rate>] [--seed <n>] [--slot <n>] [--out <file>]
+  ssz-arena generate-block [--shape typical|worst-case] [--transactions <n> --transaction-size <bytes>]
+                           [--blobs <n>] [--seed <n>] [--slot <n>] [--out <file>]
+  ssz-arena corpus [block|state|all] [--out <dir>]";
+
+fn fetch(kinds: &[FixtureKind], id: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let cache = FixtureCache::from_env()?;
+    let source = source_from_env()?;
+    let id = match id
+        .map(str::to_string)
+        .or_else(|| std::env::var("SSZ_ARENA_SLOT").ok())
+    {
+        Some(id) => id,
+        None => source.latest_slot()?.to_string(),
+    };
+    let print_fetched = |kind: FixtureKind, id: &dyn std::fmt::Display, bytes: &[u8]| {
+        println!(
+            "{} @ {id}: {} bytes, sha256 {}",
+            kind.as_str(),
+            bytes.len(),
+            sha256_hex(bytes)
+        );
+    };
+
+    // a block root is no state root and `head` may move between two requests,
+    // so the state is fetched at the slot of the block the id resolved to
+    let mut state_id = None;
+    if kinds.contains(&FixtureKind::Block) {
+        let bytes = cache.load_block_at(&*source, id.parse()?, FixtureMode::Online)?;
+        print_fetched(FixtureKind::Block, &id, &bytes);
+        let slot =
+            peek_slot(FixtureKind::Block, &bytes).ok_or_else(|| ArenaFetchError::Decode {
+                what: format!("block for {id}"),
+                reason: "too short to contain a slot".to_string(),
+            })?;
+        state_id = Some(StateId::Slot(slot));
+    }
+    if kinds.contains(&FixtureKind::State) {
+        let state_id = match state_id {
+            Some(state_id) => state_id,
+            None => id.parse()?,
+        };
+        let bytes = cache.load_state_at(&*source, state_id, FixtureMode::Online)?;
+        print_fetched(FixtureKind::State, &state_id, &bytes);
+    }
+    println!(
+        "cached under {}/{}",
+        cache.root().display(),
+        cache.network()
+    );
+    Ok(())
+}
+
+/// The `--flag value` pairs of a subcommand.
+struct Flags<'a>(Vec<(&'a str, &'a str)>);
+
+impl<'a> Flags<'a> {
+    fn get(&self, name: &str) -> Option<&'a str> {
+        self.0
+            .iter()
+            .find(|(flag, _)| *flag == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Every `--flag value` pair in `args`. Prints the usage and exits on
+/// anything else, such as an unknown flag or one without a value.
+fn flags<'a>(args: &[&'a str], known: &[&str]) -> Flags<'a> {
+    let pairs: Option<Vec<_>> = args
+        .chunks(2)
+        .map(|pair| match pair {
+            [flag, value] if known.contains(flag) => Some((*flag, *value)),
+            _ => None,
+        })
+        .collect();
+    match pairs {
+        Some(pairs) => Flags(pairs),
+        None => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    }
+}
+
+fn collect(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let flags = flags(args, &["--criterion", "--divan", "--out"]);
+    let criterion_dir = Path::new(flags.get("--criterion").unwrap_or(DEFAULT_CRITERION_DIR));
+    let out_dir = PathBuf::from(flags.get("--out").unwrap_or(DEFAULT_RESULTS_DIR));
+
+    let revs = Revisions::current();
+    let mut records = collect_criterion(criterion_dir, &revs)?;
+    println!(
+        "{} criterion results from {}",
+        records.len(),
+        criterion_dir.display()
+    );
+    // an explicit --divan must exist, the default is only picked up if present
+    let divan = match flags.get("--divan") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(PathBuf::from(DEFAULT_DIVAN_OUTPUT)).filter(|path| path.is_file()),
+    };
+    if let Some(divan) = divan {
+        let divan_records = collect_divan(&divan, &revs)?;
+        println!(
+            "{} divan results from {}",
+            divan_records.len(),
+            divan.display()
+        );
+        records.extend(divan_records);
+    }
+    if records.is_empty() {
+        return Err(ResultsError::NoResults.into());
+    }
+
+    let (json, csv) = write_results(&out_dir, &records)?;
+    println!(
+        "wrote {} and {} at {}",
+        json.display(),
+        csv.display(),
+        revs.git_rev
+    );
+    Ok(())
+}
+
+fn report(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let flags = flags(args, &["--results", "--baseline", "--format", "--out"]);
+    let results = match flags.get("--results") {
+        Some(path) => PathBuf::from(path),
+        None => Path::new(DEFAULT_RESULTS_DIR).join("results.json"),
+    };
+    let baseline = flags.get("--baseline").unwrap_or("Lighthouse");
+    let format: ReportFormat = flags.get("--format").unwrap_or("markdown").parse()?;
+
+    let records = read_results(&results)?;
+    let report = render_report(&records, baseline, format);
+    match flags.get("--out") {
+        Some(out) => {
+            std::fs::write(out, report)?;
+            eprintln!("wrote {out}");
+        }
+        None => print!("{report}"),
+    }
+    Ok(())
+}
+
+/// Exits with 1 if any benchmark regressed past the threshold or none could be compared.
+fn compare_runs(base: &str, new: &str, args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let flags = flags(args, &["--threshold"]);
+    let threshold = match flags.get("--threshold") {
+        Some(threshold) => threshold.parse()?,
+        None => DEFAULT_THRESHOLD_PERCENT,
+    };
+    let base = read_results(Path::new(base))?;
+    let new = read_results(Path::new(new))?;
+    let report = compare(&base, &new, threshold);
+    print!("{report}");
+    if !report.input_changed.is_empty() {
+        eprintln!(
+            "warning: skipped {} benchmarks whose input changed between the runs",
+            report.input_changed.len()
+        );
+    }
+    if report.comparisons.is_empty() {
+        return Err(
+            "no benchmark could be compared, the runs share none with the same input".into(),
+        );
+    }
+
+    let regressions = report.regressions().count();
+    if regressions > 0 {
+        eprintln!("{regressions} benchmarks regressed by more than {threshold}%");
+        std::process::exit(1);
+    }
+    println!(
+        "no regressions past {threshold}% across {} benchmarks",
+        report.comparisons.len()
+    );
+    Ok(())
+}
+
+fn generate_state(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let known = [
+        "--validators",
+        "--eth1-votes",
+        "--historical-summaries",
+        "--participation",
+        "--seed",
+        "--slot",
+        "--out",
+    ];
+    let flags = flags(args, &known);
+
+    let mut generator = StateGenerator::new(flags.get("--seed").unwrap_or("0").parse()?);
+    if let Some(validators) = flags.get("--validators") {
+        generator = generator.with_validators(validators.parse()?);
+    }
+    if let Some(votes) = flags.get("--eth1-votes") {
+        generator = generator.with_eth1_votes(votes.parse()?);
+    }
+    if let Some(summaries) = flags.get("--historical-summaries") {
+        generator = generator.with_historical_summaries(summaries.parse()?);
+    }
+    if let Some(participation) = flags.get("--participation") {
+        generator = generator.with_participation(participation.parse()?);
+    }
+    if let Some(slot) = flags.get("--slot") {
+        generator = generator.with_slot(slot.parse()?);
+    }
+    let out = flags.get("--out").unwrap_or("beacon-state.ssz");
+
+    let network = Network::from_env()?;
+    let bytes = with_arena_spec!(network, S => {
+        let state = generator.generate::<<S as ArenaSpec>::Preset>(network)?;
+        // the benches pick the layout from the slot, which must land in Deneb
+        let fork = network.fork_at_slot(state.slot);
+        if fork != ForkName::Deneb {
+            return Err(format!("slot {} is in {fork} on {network}, not deneb", state.slot).into());
+        }
+        sszb::SszEncode::to_ssz(&state)
+    });
+    std::fs::write(out, &bytes)?;
+    println!(
+        "wrote {out}: {} bytes, sha256 {}",
+        bytes.len(),
+        sha256_hex(&bytes)
+    );
+    Ok(())
+}
+
+fn generate_block(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let known = [
+        "--shape",
+        "--transactions",
+        "--transaction-size",
+        "--blobs",
+        "--seed",
+        "--slot",
+        "--out",
+    ];
+    let flags = flags(args, &known);
+
+    let seed = flags.get("--seed").unwrap_or("0").parse()?;
+    let mut generator = match flags.get("--shape").unwrap_or("typical") {
+        "typical" => BlockGenerator::new(seed),
+        "worst-case" => BlockGenerator::worst_case(seed),
+        shape => {
+            return Err(
+                format!("unknown block shape {shape:?}, expected typical or worst-case").into(),
+            )
+        }
+    };
+    match (flags.get("--transactions"), flags.get("--transaction-size")) {
+        (Some(count), Some(size)) => {
+            generator = generator.with_transactions(count.parse()?, size.parse()?);
+        }
+        (None, None) => {}
+        _ => return Err("--transactions and --transaction-size go together".into()),
+    }
+    if let Some(blobs) = flags.get("--blobs") {
+        generator = generator.with_blob_commitments(blobs.parse()?);
+    }
+    if let Some(slot) = flags.get("--slot") {
+        generator = generator.with_slot(slot.parse()?);
+    }
+    let out = flags.get("--out").unwrap_or("beacon-block.ssz");
+
+    let network = Network::from_env()?;
+    let bytes = with_arena_spec!(network, S => {
+        let block = generator.generate::<<S as ArenaSpec>::Preset>(network)?;
+        let fork = network.fork_at_slot(block.message.slot);
+        if fork != ForkName::Deneb {
+            return Err(format!("slot {} is in {fork} on {network}, not deneb", block.message.slot).into());
+        }
+        sszb::SszEncode::to_ssz(&block)
+    });
+    std::fs::write(out, &bytes)?;
+    println!(
+        "wrote {out}: {} bytes, sha256 {}",
+        bytes.len(),
+        sha256_hex(&bytes)
+    );
+    Ok(())
+}
+
+/// Writes every mutation of the block and/or state fixture to `<out>/<kind>-<mutation>.ssz`.
+fn corpus(kinds: &[FixtureKind], args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let flags = flags(args, &["--out"]);
+    let out = PathBuf::from(flags.get("--out").unwrap_or(DEFAULT_CORPUS_DIR));
+    std::fs::create_dir_all(&out)?;
+
+    let network = Network::from_env()?;
+    for kind in kinds {
+        let bytes = match kind {
+            FixtureKind::Block => load_block_bytes()?,
+            FixtureKind::State => load_state_bytes()?,
+        };
+        let corpus = with_arena_spec!(network, S => malformed_corpus::<S>(*kind, &bytes));
+        for (mutation, bytes) in corpus {
+            let path = out.join(format!(
+                "{}-{}.ssz",
+                kind.as_str(),
+                mutation.as_str().replace(' ', "-")
+            ));
+            std::fs::write(&path, &bytes)?;
+            println!("wrote {}: {} bytes", path.display(), bytes.len());
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["fetch"] => fetch(&[FixtureKind::Block, FixtureKind::State], None),
+        ["fetch", "all", id @ ..] if id.len() <= 1 => fetch(
+            &[FixtureKind::Block, FixtureKind::State],
+            id.first().copied(),
+        ),
+        ["fetch", "block", id @ ..] if id.len() <= 1 => {
+            fetch(&[FixtureKind::Block], id.first().copied())
+        }
+        ["fetch", "state", id @ ..] if id.len() <= 1 => {
+            fetch(&[FixtureKind::State], id.first().copied())
+        }
+        ["collect", args @ ..] => collect(args),
+        ["report", args @ ..] => report(args),
+        ["compare", base, new, args @ ..] => compare_runs(base, new, args),
+        ["generate-state", args @ ..] => generate_state(args),
+        ["generate-block", args @ ..] => generate_block(args),
+        ["corpus", "block", args @ ..] => corpus(&[FixtureKind::Block], args),
+        ["corpus", "state", args @ ..] => corpus(&[FixtureKind::State], args),
+        ["corpus", "all", args @ ..] => corpus(&[FixtureKind::Block, FixtureKind::State], args),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
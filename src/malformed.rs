@@ -0,0 +1,447 @@
+use std::any::Any;
+use std::fmt;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use typenum::Unsigned;
+
+use crate::schema::{self, FieldLayout, Schema, OFFSET};
+use crate::{
+    peek_slot, ArenaSpec, BeaconStateAnyFork, FixtureKind, ForkName, Preset,
+    SignedBeaconBlockAnyFork,
+};
+
+/// `MAX_VOLUNTARY_EXITS`, the block list the oversized mutation overflows.
+const MAX_VOLUNTARY_EXITS: usize = 16;
+
+/// One way of corrupting a valid block or state. Offset mutations target the
+/// block body's or the state's own offsets, where decoders do the most work.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mutation {
+    /// The last offset points past the end of the input.
+    OffsetOutOfBounds,
+    /// Two neighbouring offsets swapped, so the second points before the
+    /// first. The first offset is left valid.
+    DecreasingOffsets,
+    /// The first offset points back into the fixed part.
+    OffsetIntoFixedPart,
+    /// A list grown past its limit: voluntary exits in blocks, eth1 data
+    /// votes in states.
+    OversizedList,
+    /// The input cut off one byte before its last variable-size field starts.
+    TruncatedTail,
+    /// A bitlist ending in a zero byte, so it has no length bit, in blocks,
+    /// and bits set past the end of `justification_bits` in states.
+    BitfieldPadding,
+}
+
+impl Mutation {
+    pub const ALL: [Self; 6] = [
+        Self::OffsetOutOfBounds,
+        Self::DecreasingOffsets,
+        Self::OffsetIntoFixedPart,
+        Self::OversizedList,
+        Self::TruncatedTail,
+        Self::BitfieldPadding,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OffsetOutOfBounds => "offset out of bounds",
+            Self::DecreasingOffsets => "decreasing offsets",
+            Self::OffsetIntoFixedPart => "offset into fixed part",
+            Self::OversizedList => "oversized list",
+            Self::TruncatedTail => "truncated tail",
+            Self::BitfieldPadding => "bitfield padding",
+        }
+    }
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn write_offset(bytes: &mut [u8], at: usize, offset: usize) {
+    bytes[at..at + OFFSET].copy_from_slice(&(offset as u32).to_le_bytes());
+}
+
+/// Absolute range and schema of field `name` of the container at `range`.
+fn field<'a>(
+    container: &'a Schema,
+    bytes: &[u8],
+    range: Range<usize>,
+    name: &str,
+) -> Option<(Range<usize>, &'a Schema)> {
+    let start = range.start;
+    let field = container
+        .fields(bytes.get(range)?)?
+        .into_iter()
+        .find(|field| field.name == name)?;
+    Some((
+        start + field.range.start..start + field.range.end,
+        field.schema,
+    ))
+}
+
+/// Applies an offset or length mutation to the container at `start`, which
+/// must run to the end of `bytes`. `oversized` names the list to overflow
+/// and its limit.
+fn mutate_container(
+    bytes: &[u8],
+    container: &Schema,
+    start: usize,
+    mutation: Mutation,
+    oversized: (&str, usize),
+) -> Option<Vec<u8>> {
+    let layout = container.fields(&bytes[start..])?;
+    let variable: Vec<&FieldLayout> = layout
+        .iter()
+        .filter(|field| field.offset_at.is_some())
+        .collect();
+    let mut out = bytes.to_vec();
+    match mutation {
+        Mutation::OffsetOutOfBounds => {
+            let last = variable.last()?;
+            write_offset(&mut out, start + last.offset_at?, bytes.len() - start + 1);
+        }
+        Mutation::DecreasingOffsets => {
+            // leave the first offset alone, or this is OffsetIntoFixedPart again
+            let [a, b] = variable
+                .get(1..)?
+                .windows(2)
+                .find(|pair| pair[0].range.start < pair[1].range.start)?
+            else {
+                return None;
+            };
+            write_offset(&mut out, start + a.offset_at?, b.range.start);
+            write_offset(&mut out, start + b.offset_at?, a.range.start);
+        }
+        Mutation::OffsetIntoFixedPart => {
+            let first = variable.first()?;
+            write_offset(
+                &mut out,
+                start + first.offset_at?,
+                first.range.start.checked_sub(OFFSET)?,
+            );
+        }
+        Mutation::OversizedList => {
+            let (name, limit) = oversized;
+            let index = variable.iter().position(|field| field.name == name)?;
+            let Schema::List(elem, _) = variable[index].schema else {
+                return None;
+            };
+            // limit + 1 more elements overflow the list whatever it held
+            let extra = (limit + 1) * elem.fixed_len()?;
+            let end = start + variable[index].range.end;
+            out.splice(end..end, std::iter::repeat(0).take(extra));
+            for later in &variable[index + 1..] {
+                write_offset(
+                    &mut out,
+                    start + later.offset_at?,
+                    later.range.start + extra,
+                );
+            }
+        }
+        Mutation::TruncatedTail => {
+            let last = variable.last()?;
+            out.truncate((start + last.range.start).checked_sub(1)?);
+        }
+        Mutation::BitfieldPadding => return None,
+    }
+    Some(out)
+}
+
+fn malform_block<P: Preset>(bytes: &[u8], fork: ForkName, mutation: Mutation) -> Option<Vec<u8>> {
+    let schema = schema::signed_beacon_block::<P>(fork);
+    let (message, message_schema) = field(&schema, bytes, 0..bytes.len(), "message")?;
+    let (body, body_schema) = field(message_schema, bytes, message, "body")?;
+    if mutation != Mutation::BitfieldPadding {
+        return mutate_container(
+            bytes,
+            body_schema,
+            body.start,
+            mutation,
+            ("voluntary_exits", MAX_VOLUNTARY_EXITS),
+        );
+    }
+
+    let (attestations, list) = field(body_schema, bytes, body, "attestations")?;
+    let (first, attestation) = list.element(&bytes[attestations.clone()], 0)?;
+    let first = attestations.start + first.start..attestations.start + first.end;
+    let (bits, _) = field(attestation, bytes, first, "aggregation_bits")?;
+    let mut out = bytes.to_vec();
+    out[bits.end.checked_sub(1)?] = 0;
+    Some(out)
+}
+
+fn malform_state<P: Preset>(bytes: &[u8], fork: ForkName, mutation: Mutation) -> Option<Vec<u8>> {
+    let schema = schema::beacon_state::<P>(fork);
+    if mutation != Mutation::BitfieldPadding {
+        return mutate_container(
+            bytes,
+            &schema,
+            0,
+            mutation,
+            ("eth1_data_votes", P::SlotsPerEth1VotingPeriod::to_usize()),
+        );
+    }
+
+    // a Bitvector[4], so the upper half of its byte is padding
+    let (bits, _) = field(&schema, bytes, 0..bytes.len(), "justification_bits")?;
+    let mut out = bytes.to_vec();
+    out[bits.start] |= 0xf0;
+    Some(out)
+}
+
+/// `bytes`, a valid block or state, corrupted by `mutation`. `None` if the
+/// input has nothing to corrupt that way, e.g. a block without attestations.
+pub fn malform<S: ArenaSpec>(
+    kind: FixtureKind,
+    bytes: &[u8],
+    mutation: Mutation,
+) -> Option<Vec<u8>> {
+    let fork = S::NETWORK.fork_at_slot(peek_slot(kind, bytes)?);
+    match kind {
+        FixtureKind::Block => malform_block::<S::Preset>(bytes, fork, mutation),
+        FixtureKind::State => malform_state::<S::Preset>(bytes, fork, mutation),
+    }
+}
+
+/// Every mutation [`malform`] can apply to `bytes`.
+pub fn malformed_corpus<S: ArenaSpec>(kind: FixtureKind, bytes: &[u8]) -> Vec<(Mutation, Vec<u8>)> {
+    Mutation::ALL
+        .into_iter()
+        .filter_map(|mutation| Some((mutation, malform::<S>(kind, bytes, mutation)?)))
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum RejectError {
+    /// A library decoded the malformed input without an error.
+    Accepted { library: &'static str },
+    Panicked {
+        library: &'static str,
+        message: String,
+    },
+}
+
+impl fmt::Display for RejectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accepted { library } => write!(f, "{library} accepted malformed input"),
+            Self::Panicked { library, message } => {
+                write!(f, "{library} panicked on malformed input: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RejectError {}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or_else(|| "non-string panic payload".to_string(), |s| s.to_string()),
+    }
+}
+
+/// `Ok` if `is_err` returned true, i.e. the library rejected the input.
+fn expect_rejected(
+    library: &'static str,
+    is_err: impl FnOnce() -> bool,
+) -> Result<(), RejectError> {
+    match panic::catch_unwind(AssertUnwindSafe(is_err)) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(RejectError::Accepted { library }),
+        Err(payload) => Err(RejectError::Panicked {
+            library,
+            message: panic_message(payload),
+        }),
+    }
+}
+
+/// Decodes malformed `bytes` as a `fork` block or state with sszb and, when
+/// their features are enabled, lighthouse and grandine, and checks that each
+/// returns an error rather than a value or a panic. Returns the libraries
+/// that took part.
+pub fn check_rejected<S: ArenaSpec>(
+    kind: FixtureKind,
+    bytes: &[u8],
+    fork: ForkName,
+) -> Result<Vec<&'static str>, RejectError> {
+    let mut libraries = Vec::new();
+    match kind {
+        FixtureKind::Block => expect_rejected("sszb", || {
+            SignedBeaconBlockAnyFork::<S::Preset>::from_ssz_bytes_for_fork(bytes, fork).is_err()
+        })?,
+        FixtureKind::State => expect_rejected("sszb", || {
+            BeaconStateAnyFork::<S::Preset>::from_ssz_bytes_for_fork(bytes, fork).is_err()
+        })?,
+    }
+    libraries.push("sszb");
+
+    #[cfg(feature = "sigp")]
+    {
+        match kind {
+            FixtureKind::Block => expect_rejected("lighthouse", || {
+                sigp_types::SignedBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(
+                    bytes,
+                    fork.into(),
+                )
+                .is_err()
+            })?,
+            FixtureKind::State => expect_rejected("lighthouse", || {
+                sigp_types::BeaconState::<S::Sigp>::from_ssz_bytes(bytes, &S::sigp_spec()).is_err()
+            })?,
+        }
+        libraries.push("lighthouse");
+    }
+
+    #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        use grandine_ssz::SszRead;
+
+        let config = S::grandine_config();
+        match kind {
+            FixtureKind::Block => expect_rejected("grandine", || {
+                grandine_types::combined::SignedBeaconBlock::<S::Grandine>::from_ssz(&config, bytes)
+                    .is_err()
+            })?,
+            FixtureKind::State => expect_rejected("grandine", || {
+                grandine_types::combined::BeaconState::<S::Grandine>::from_ssz(&config, bytes)
+                    .is_err()
+            })?,
+        }
+        libraries.push("grandine");
+    }
+
+    Ok(libraries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use sszb::SszEncode;
+
+    use super::*;
+    use crate::{BlockGenerator, MainnetSpec, Network};
+
+    type P = <MainnetSpec as ArenaSpec>::Preset;
+
+    fn read_offset(bytes: &[u8], at: usize) -> usize {
+        u32::from_le_bytes(bytes[at..at + OFFSET].try_into().unwrap()) as usize
+    }
+
+    /// Start of the block body, and the absolute position of each variable-size
+    /// body field's offset and the range it points at.
+    fn body_layout(bytes: &[u8]) -> (usize, BTreeMap<&'static str, (usize, Range<usize>)>) {
+        let schema = schema::signed_beacon_block::<P>(ForkName::Deneb);
+        let (message, message_schema) = field(&schema, bytes, 0..bytes.len(), "message").unwrap();
+        let (body, body_schema) = field(message_schema, bytes, message, "body").unwrap();
+        let layout = body_schema
+            .fields(&bytes[body.clone()])
+            .unwrap()
+            .into_iter()
+            .filter_map(|field| {
+                let range = body.start + field.range.start..body.start + field.range.end;
+                Some((field.name, (body.start + field.offset_at?, range)))
+            })
+            .collect();
+        (body.start, layout)
+    }
+
+    /// Fields of `before` holding a byte that differs in `after`.
+    fn changed_fields(before: &[u8], after: &[u8]) -> BTreeSet<String> {
+        let schema = schema::signed_beacon_block::<P>(ForkName::Deneb);
+        assert_eq!(before.len(), after.len());
+        (0..before.len())
+            .filter(|&i| before[i] != after[i])
+            .map(|i| schema.path_at(before, i))
+            .collect()
+    }
+
+    fn fields(paths: &[&str]) -> BTreeSet<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    #[test]
+    fn mutations_corrupt_the_intended_bytes_and_are_rejected() {
+        // attestations, a payload and three blobs, but no slashings, deposits,
+        // exits or BLS changes
+        let before = BlockGenerator::new(0)
+            .generate::<P>(Network::Mainnet)
+            .unwrap()
+            .to_ssz();
+        let (body_start, layout) = body_layout(&before);
+
+        for mutation in Mutation::ALL {
+            let after = malform::<MainnetSpec>(FixtureKind::Block, &before, mutation)
+                .unwrap_or_else(|| panic!("{mutation} doesn't apply to a generated block"));
+            match mutation {
+                Mutation::OffsetOutOfBounds => {
+                    let (at, _) = layout["blob_kzg_commitments"];
+                    assert_eq!(
+                        changed_fields(&before, &after),
+                        fields(&[".message.body.blob_kzg_commitments"])
+                    );
+                    assert_eq!(read_offset(&after, at), before.len() - body_start + 1);
+                }
+                Mutation::DecreasingOffsets => {
+                    // proposer and attester slashings are empty, so the
+                    // first pair that can decrease is attestations and deposits
+                    assert_eq!(
+                        changed_fields(&before, &after),
+                        fields(&[".message.body.attestations", ".message.body.deposits"])
+                    );
+                    let (attestations, _) = layout["attestations"];
+                    let (deposits, _) = layout["deposits"];
+                    assert!(read_offset(&after, attestations) > read_offset(&after, deposits));
+                }
+                Mutation::OffsetIntoFixedPart => {
+                    let (at, _) = layout["proposer_slashings"];
+                    assert_eq!(
+                        changed_fields(&before, &after),
+                        fields(&[".message.body.proposer_slashings"])
+                    );
+                    assert_eq!(read_offset(&after, at), read_offset(&before, at) - OFFSET);
+                }
+                Mutation::OversizedList => {
+                    // a SignedVoluntaryExit is 112 bytes
+                    let extra = (MAX_VOLUNTARY_EXITS + 1) * 112;
+                    assert_eq!(after.len(), before.len() + extra);
+                    let (_, grown) = body_layout(&after);
+                    assert_eq!(grown["voluntary_exits"].1.len(), extra);
+                    for name in [
+                        "execution_payload",
+                        "bls_to_execution_changes",
+                        "blob_kzg_commitments",
+                    ] {
+                        assert_eq!(grown[name].1.start, layout[name].1.start + extra, "{name}");
+                        assert_eq!(grown[name].1.len(), layout[name].1.len(), "{name}");
+                    }
+                }
+                Mutation::TruncatedTail => {
+                    let (_, blobs) = &layout["blob_kzg_commitments"];
+                    assert_eq!(after.len(), blobs.start - 1);
+                    assert!(before.starts_with(&after));
+                }
+                Mutation::BitfieldPadding => {
+                    assert_eq!(
+                        changed_fields(&before, &after),
+                        fields(&[".message.body.attestations[0].aggregation_bits"])
+                    );
+                }
+            }
+            if let Err(e) =
+                check_rejected::<MainnetSpec>(FixtureKind::Block, &after, ForkName::Deneb)
+            {
+                panic!("{mutation}: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use std::fmt;
+use std::str::FromStr;
+
+use grandine_types::config::Config;
+use sigp_types::{ChainSpec, Epoch, EthSpec, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec};
+
+use crate::{ArenaFetchError, GnosisPreset, MainnetPreset, MinimalPreset, Preset};
+
+const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Holesky,
+    Sepolia,
+    Gnosis,
+    /// Minimal preset, which only exists on local devnets.
+    Minimal,
+}
+
+/// Fork versions of every fork, as stamped into a state's `fork` field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForkVersions {
+    pub genesis: [u8; 4],
+    pub altair: [u8; 4],
+    pub bellatrix: [u8; 4],
+    pub capella: [u8; 4],
+    pub deneb: [u8; 4],
+    pub electra: [u8; 4],
+}
+
+/// Activation epochs of every post-genesis fork, `u64::MAX` if unscheduled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForkEpochs {
+    pub altair: u64,
+    pub bellatrix: u64,
+    pub capella: u64,
+    pub deneb: u64,
+    pub electra: u64,
+}
+
+impl Network {
+    pub const ALL: [Network; 5] = [
+        Self::Mainnet,
+        Self::Holesky,
+        Self::Sepolia,
+        Self::Gnosis,
+        Self::Minimal,
+    ];
+
+    /// `SSZ_ARENA_NETWORK` selects the network, mainnet by default.
+    pub fn from_env() -> Result<Self, ArenaFetchError> {
+        match std::env::var("SSZ_ARENA_NETWORK") {
+            Ok(network) => network.parse(),
+            Err(_) => Ok(Self::Mainnet),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Holesky => "holesky",
+            Self::Sepolia => "sepolia",
+            Self::Gnosis => "gnosis",
+            Self::Minimal => "minimal",
+        }
+    }
+
+    pub fn preset_name(&self) -> &'static str {
+        match self {
+            Self::Mainnet | Self::Holesky | Self::Sepolia => MainnetPreset::NAME,
+            Self::Gnosis => GnosisPreset::NAME,
+            Self::Minimal => MinimalPreset::NAME,
+        }
+    }
+
+    /// Public checkpointz server, if the network has one.
+    pub fn checkpointz_url(&self) -> Option<&'static str> {
+        match self {
+            Self::Mainnet => Some("https://sync-mainnet.beaconcha.in"),
+            Self::Holesky => Some("https://checkpoint-sync.holesky.ethpandaops.io"),
+            Self::Sepolia => Some("https://checkpoint-sync.sepolia.ethpandaops.io"),
+            Self::Gnosis => Some("https://checkpoint.gnosischain.com"),
+            Self::Minimal => None,
+        }
+    }
+
+    pub fn slots_per_epoch(&self) -> u64 {
+        match self {
+            Self::Mainnet | Self::Holesky | Self::Sepolia => 32,
+            Self::Gnosis => 16,
+            Self::Minimal => 8,
+        }
+    }
+
+    /// Beacon chain genesis time. Local devnets pick their own, so the
+    /// minimal network uses the preset's `MIN_GENESIS_TIME`.
+    pub fn genesis_time(&self) -> u64 {
+        match self {
+            Self::Mainnet => 1_606_824_023,
+            Self::Holesky => 1_695_902_400,
+            Self::Sepolia => 1_655_733_600,
+            Self::Gnosis => 1_638_993_340,
+            Self::Minimal => 1_578_009_600,
+        }
+    }
+
+    pub fn fork_versions(&self) -> ForkVersions {
+        // written as the configs do, e.g. `0x04000000` for mainnet's deneb
+        let versions =
+            |[genesis, altair, bellatrix, capella, deneb, electra]: [u32; 6]| ForkVersions {
+                genesis: genesis.to_be_bytes(),
+                altair: altair.to_be_bytes(),
+                bellatrix: bellatrix.to_be_bytes(),
+                capella: capella.to_be_bytes(),
+                deneb: deneb.to_be_bytes(),
+                electra: electra.to_be_bytes(),
+            };
+        match self {
+            Self::Mainnet => versions([
+                0x00000000, 0x01000000, 0x02000000, 0x03000000, 0x04000000, 0x05000000,
+            ]),
+            Self::Holesky => versions([
+                0x01017000, 0x02017000, 0x03017000, 0x04017000, 0x05017000, 0x06017000,
+            ]),
+            Self::Sepolia => versions([
+                0x90000069, 0x90000070, 0x90000071, 0x90000072, 0x90000073, 0x90000074,
+            ]),
+            Self::Gnosis => versions([
+                0x00000064, 0x01000064, 0x02000064, 0x03000064, 0x04000064, 0x05000064,
+            ]),
+            Self::Minimal => versions([
+                0x00000001, 0x01000001, 0x02000001, 0x03000001, 0x04000001, 0x05000001,
+            ]),
+        }
+    }
+
+    pub fn fork_epochs(&self) -> ForkEpochs {
+        match self {
+            Self::Mainnet => ForkEpochs {
+                altair: 74240,
+                bellatrix: 144896,
+                capella: 194048,
+                deneb: 269568,
+                electra: 364032,
+            },
+            Self::Holesky => ForkEpochs {
+                altair: 0,
+                bellatrix: 0,
+                capella: 256,
+                deneb: 29696,
+                electra: 115968,
+            },
+            Self::Sepolia => ForkEpochs {
+                altair: 50,
+                bellatrix: 100,
+                capella: 56832,
+                deneb: 132608,
+                electra: 222464,
+            },
+            Self::Gnosis => ForkEpochs {
+                altair: 512,
+                bellatrix: 385536,
+                capella: 648704,
+                deneb: 889856,
+                electra: 1337856,
+            },
+            // local devnets are assumed to launch straight into the latest fork
+            Self::Minimal => ForkEpochs {
+                altair: 0,
+                bellatrix: 0,
+                capella: 0,
+                deneb: 0,
+                electra: 0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Network {
+    type Err = ArenaFetchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|network| network.as_str() == s)
+            .ok_or_else(|| ArenaFetchError::Decode {
+                what: "network".to_string(),
+                reason: format!("unknown network {s:?}"),
+            })
+    }
+}
+
+/// Ties a network to the spec types each library decodes it with.
+pub trait ArenaSpec: 'static {
+    const NETWORK: Network;
+    /// grandine_types has no gnosis preset, so its gnosis benchmarks are skipped.
+    const GRANDINE_SUPPORTED: bool = true;
+
+    type Preset: Preset;
+    type Sigp: EthSpec;
+    type Grandine: grandine_types::preset::Preset;
+
+    fn sigp_base_spec() -> ChainSpec;
+    fn grandine_config() -> Config;
+
+    /// Lighthouse infers the fork from the slot, so its spec must carry the
+    /// network's fork schedule rather than the base preset's.
+    fn sigp_spec() -> ChainSpec {
+        let epoch = |epoch: u64| (epoch != FAR_FUTURE_EPOCH).then(|| Epoch::new(epoch));
+        let forks = Self::NETWORK.fork_epochs();
+        let mut spec = Self::sigp_base_spec();
+        spec.altair_fork_epoch = epoch(forks.altair);
+        spec.bellatrix_fork_epoch = epoch(forks.bellatrix);
+        spec.capella_fork_epoch = epoch(forks.capella);
+        spec.deneb_fork_epoch = epoch(forks.deneb);
+        spec.electra_fork_epoch = epoch(forks.electra);
+        spec
+    }
+}
+
+pub struct MainnetSpec;
+pub struct HoleskySpec;
+pub struct SepoliaSpec;
+pub struct GnosisSpec;
+pub struct MinimalSpec;
+
+impl ArenaSpec for MainnetSpec {
+    const NETWORK: Network = Network::Mainnet;
+    type Preset = MainnetPreset;
+    type Sigp = MainnetEthSpec;
+    type Grandine = grandine_types::preset::Mainnet;
+
+    fn sigp_base_spec() -> ChainSpec {
+        ChainSpec::mainnet()
+    }
+
+    fn grandine_config() -> Config {
+        Config::mainnet()
+    }
+}
+
+impl ArenaSpec for HoleskySpec {
+    const NETWORK: Network = Network::Holesky;
+    type Preset = MainnetPreset;
+    type Sigp = MainnetEthSpec;
+    type Grandine = grandine_types::preset::Mainnet;
+
+    fn sigp_base_spec() -> ChainSpec {
+        ChainSpec::mainnet()
+    }
+
+    fn grandine_config() -> Config {
+        Config::holesky()
+    }
+}
+
+impl ArenaSpec for SepoliaSpec {
+    const NETWORK: Network = Network::Sepolia;
+    type Preset = MainnetPreset;
+    type Sigp = MainnetEthSpec;
+    type Grandine = grandine_types::preset::Mainnet;
+
+    fn sigp_base_spec() -> ChainSpec {
+        ChainSpec::mainnet()
+    }
+
+    fn grandine_config() -> Config {
+        Config::sepolia()
+    }
+}
+
+impl ArenaSpec for GnosisSpec {
+    const NETWORK: Network = Network::Gnosis;
+    const GRANDINE_SUPPORTED: bool = false;
+    type Preset = GnosisPreset;
+    type Sigp = GnosisEthSpec;
+    type Grandine = grandine_types::preset::Mainnet;
+
+    fn sigp_base_spec() -> ChainSpec {
+        ChainSpec::gnosis()
+    }
+
+    fn grandine_config() -> Config {
+        Config::mainnet()
+    }
+}
+
+impl ArenaSpec for MinimalSpec {
+    const NETWORK: Network = Network::Minimal;
+    type Preset = MinimalPreset;
+    type Sigp = MinimalEthSpec;
+    type Grandine = grandine_types::preset::Minimal;
+
+    fn sigp_base_spec() -> ChainSpec {
+        ChainSpec::minimal()
+    }
+
+    fn grandine_config() -> Config {
+        Config::minimal()
+    }
+}
+
+/// Runs `$body` with `$spec` bound to the [`ArenaSpec`] of `$network`.
+#[macro_export]
+macro_rules! with_arena_spec {
+    ($network:expr, $spec:ident => $body:expr) => {
+        match $network {
+            $crate::Network::Mainnet => {
+                type $spec = $crate::MainnetSpec;
+                $body
+            }
+            $crate::Network::Holesky => {
+                type $spec = $crate::HoleskySpec;
+                $body
+            }
+            $crate::Network::Sepolia => {
+                type $spec = $crate::SepoliaSpec;
+                $body
+            }
+            $crate::Network::Gnosis => {
+                type $spec = $crate::GnosisSpec;
+                $body
+            }
+            $crate::Network::Minimal => {
+                type $spec = $crate::MinimalSpec;
+                $body
+            }
+        }
+    };
+}
@@ -0,0 +1,102 @@
+use std::fmt::Debug;
+use typenum::Unsigned;
+
+/// Preset-dependent list and vector lengths used by the arena's containers.
+///
+/// Only the lengths that differ between mainnet, gnosis and minimal are here,
+/// everything else is spelled out as a typenum in the container itself.
+pub trait Preset: 'static + Clone + Copy + PartialEq + Eq + Debug + Default + Send + Sync {
+    type SlotsPerEpoch: Unsigned;
+    type SlotsPerHistoricalRoot: Unsigned;
+    type EpochsPerHistoricalVector: Unsigned;
+    type EpochsPerSlashingsVector: Unsigned;
+    /// `EPOCHS_PER_ETH1_VOTING_PERIOD * SLOTS_PER_EPOCH`
+    type SlotsPerEth1VotingPeriod: Unsigned;
+    type SyncCommitteeSize: Unsigned;
+    type MaxWithdrawalsPerPayload: Unsigned;
+    type MaxBlobCommitmentsPerBlock: Unsigned;
+    /// `MAX_ATTESTATIONS * SLOTS_PER_EPOCH`, bounds phase0's epoch attestations.
+    type MaxPendingAttestations: Unsigned;
+    type PendingPartialWithdrawalsLimit: Unsigned;
+    type PendingConsolidationsLimit: Unsigned;
+    type MaxCommitteesPerSlot: Unsigned;
+    /// `MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT`, bounds Electra's aggregation bits.
+    type MaxValidatorsPerSlot: Unsigned;
+    type MaxDepositRequestsPerPayload: Unsigned;
+    type MaxWithdrawalRequestsPerPayload: Unsigned;
+    type MaxConsolidationRequestsPerPayload: Unsigned;
+
+    const NAME: &'static str;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MainnetPreset;
+
+impl Preset for MainnetPreset {
+    type SlotsPerEpoch = typenum::U32;
+    type SlotsPerHistoricalRoot = typenum::U8192;
+    type EpochsPerHistoricalVector = typenum::U65536;
+    type EpochsPerSlashingsVector = typenum::U8192;
+    type SlotsPerEth1VotingPeriod = typenum::U2048;
+    type SyncCommitteeSize = typenum::U512;
+    type MaxWithdrawalsPerPayload = typenum::U16;
+    type MaxBlobCommitmentsPerBlock = typenum::U4096;
+    type MaxPendingAttestations = typenum::U4096;
+    type PendingPartialWithdrawalsLimit = typenum::U134217728;
+    type PendingConsolidationsLimit = typenum::U262144;
+    type MaxCommitteesPerSlot = typenum::U64;
+    type MaxValidatorsPerSlot = typenum::U131072;
+    type MaxDepositRequestsPerPayload = typenum::U8192;
+    type MaxWithdrawalRequestsPerPayload = typenum::U16;
+    type MaxConsolidationRequestsPerPayload = typenum::U2;
+
+    const NAME: &'static str = "mainnet";
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct GnosisPreset;
+
+impl Preset for GnosisPreset {
+    type SlotsPerEpoch = typenum::U16;
+    type SlotsPerHistoricalRoot = typenum::U8192;
+    type EpochsPerHistoricalVector = typenum::U65536;
+    type EpochsPerSlashingsVector = typenum::U8192;
+    type SlotsPerEth1VotingPeriod = typenum::U1024;
+    type SyncCommitteeSize = typenum::U512;
+    type MaxWithdrawalsPerPayload = typenum::U8;
+    type MaxBlobCommitmentsPerBlock = typenum::U4096;
+    type MaxPendingAttestations = typenum::U2048;
+    type PendingPartialWithdrawalsLimit = typenum::U134217728;
+    type PendingConsolidationsLimit = typenum::U262144;
+    type MaxCommitteesPerSlot = typenum::U64;
+    type MaxValidatorsPerSlot = typenum::U131072;
+    type MaxDepositRequestsPerPayload = typenum::U8192;
+    type MaxWithdrawalRequestsPerPayload = typenum::U16;
+    type MaxConsolidationRequestsPerPayload = typenum::U2;
+
+    const NAME: &'static str = "gnosis";
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MinimalPreset;
+
+impl Preset for MinimalPreset {
+    type SlotsPerEpoch = typenum::U8;
+    type SlotsPerHistoricalRoot = typenum::U64;
+    type EpochsPerHistoricalVector = typenum::U64;
+    type EpochsPerSlashingsVector = typenum::U64;
+    type SlotsPerEth1VotingPeriod = typenum::U32;
+    type SyncCommitteeSize = typenum::U32;
+    type MaxWithdrawalsPerPayload = typenum::U4;
+    type MaxBlobCommitmentsPerBlock = typenum::U32;
+    type MaxPendingAttestations = typenum::U1024;
+    type PendingPartialWithdrawalsLimit = typenum::U64;
+    type PendingConsolidationsLimit = typenum::U64;
+    type MaxCommitteesPerSlot = typenum::U4;
+    type MaxValidatorsPerSlot = typenum::U8192;
+    type MaxDepositRequestsPerPayload = typenum::U4;
+    type MaxWithdrawalRequestsPerPayload = typenum::U2;
+    type MaxConsolidationRequestsPerPayload = typenum::U2;
+
+    const NAME: &'static str = "minimal";
+}
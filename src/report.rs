@@ -0,0 +1,427 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::BenchRecord;
+
+/// Objects in the order the README discusses them; anything else follows alphabetically.
+const OBJECT_ORDER: [&str; 4] = [
+    "List<u64>",
+    "ExecutionPayloadHeader List",
+    "SignedBeaconBlock",
+    "BeaconState",
+];
+const LIBRARY_ORDER: [&str; 3] = ["Sszb", "Lighthouse", "Grandine"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            s => Err(format!(
+                "unknown report format {s:?}, expected markdown or html"
+            )),
+        }
+    }
+}
+
+/// `1.35 ms`, in the largest unit that keeps the value at or above one.
+pub fn format_ns(ns: f64) -> String {
+    let (value, unit) = if ns >= 1e9 {
+        (ns / 1e9, "s")
+    } else if ns >= 1e6 {
+        (ns / 1e6, "ms")
+    } else if ns >= 1e3 {
+        (ns / 1e3, "µs")
+    } else {
+        (ns, "ns")
+    };
+    let digits = if value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+    format!("{value:.digits$} {unit}")
+}
+
+fn rank<'a>(order: &[&str], name: &'a str) -> (usize, &'a str) {
+    (
+        order.iter().position(|o| *o == name).unwrap_or(order.len()),
+        name,
+    )
+}
+
+/// One leaderboard: an object on one input, e.g. `SignedBeaconBlock` on holesky.
+struct Table<'a> {
+    title: String,
+    libraries: Vec<&'a str>,
+    /// row label to (library, mean)
+    rows: BTreeMap<(usize, String), BTreeMap<&'a str, f64>>,
+}
+
+fn tables(records: &[BenchRecord]) -> Vec<Table<'_>> {
+    let sources: BTreeSet<&str> = records.iter().map(|r| r.source.as_str()).collect();
+    let mut by_title: BTreeMap<((usize, &str), String), Vec<&BenchRecord>> = BTreeMap::new();
+    for record in records {
+        // fixture inputs are `<network>/<kind>/<digest>`, name the network unless it's mainnet
+        let title = match record.input.split_once('/') {
+            Some((network, _)) if network != "mainnet" => {
+                format!("{} ({network})", record.object)
+            }
+            _ => record.object.clone(),
+        };
+        by_title
+            .entry((rank(&OBJECT_ORDER, &record.object), title))
+            .or_default()
+            .push(record);
+    }
+
+    by_title
+        .into_iter()
+        .map(|((_, title), records)| {
+            let mut libraries: Vec<&str> = records.iter().map(|r| r.library.as_str()).collect();
+            libraries.sort_by_key(|library| rank(&LIBRARY_ORDER, library));
+            libraries.dedup();
+
+            let mut rows: BTreeMap<(usize, String), BTreeMap<&str, f64>> = BTreeMap::new();
+            for record in records {
+                // criterion and divan time differently, so they never share a row
+                let label = if sources.len() > 1 {
+                    format!("{} ({})", record.operation, record.source)
+                } else {
+                    record.operation.clone()
+                };
+                let order = match record.operation.split(' ').next() {
+                    Some("decode") => 0,
+                    Some("encode") => 1,
+                    Some("hash_tree_root") => 2,
+                    _ => 3,
+                };
+                rows.entry((order, label))
+                    .or_default()
+                    .insert(&record.library, record.mean_ns);
+            }
+            Table {
+                title,
+                libraries,
+                rows,
+            }
+        })
+        .collect()
+}
+
+/// A row's cells, `None` where a library has no measurement, and the winning library.
+fn cells<'a>(
+    table: &Table<'a>,
+    means: &BTreeMap<&'a str, f64>,
+    baseline: &str,
+) -> (Vec<Option<String>>, Option<&'a str>) {
+    let winner = (means.len() > 1)
+        .then(|| {
+            means
+                .iter()
+                .min_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(library, _)| *library)
+        })
+        .flatten();
+    let base = means.get(baseline).copied();
+    let cells = table
+        .libraries
+        .iter()
+        .map(|library| {
+            let mean = *means.get(library)?;
+            Some(match base {
+                Some(base) => format!("{} ({:.2}x)", format_ns(mean), base / mean),
+                None => format_ns(mean),
+            })
+        })
+        .collect();
+    (cells, winner)
+}
+
+fn header(records: &[BenchRecord], baseline: &str) -> String {
+    let git_revs: BTreeSet<&str> = records.iter().map(|r| r.git_rev.as_str()).collect();
+    let sszb_revs: BTreeSet<&str> = records
+        .iter()
+        .filter_map(|r| r.sszb_rev.as_deref())
+        .collect();
+    let join = |revs: BTreeSet<&str>| revs.into_iter().collect::<Vec<_>>().join(", ");
+    let mut header = format!(
+        "Mean time per operation, with the speedup over {baseline} in parentheses. Fastest in bold. Arena rev {}",
+        join(git_revs)
+    );
+    if !sszb_revs.is_empty() {
+        let _ = write!(header, ", sszb rev {}", join(sszb_revs));
+    }
+    header.push('.');
+    header
+}
+
+pub fn render_markdown(records: &[BenchRecord], baseline: &str) -> String {
+    let mut out = format!("## Leaderboard\n\n{}\n", header(records, baseline));
+    for table in tables(records) {
+        // keep `List<u64>` from being read as an html tag
+        let title = table.title.replace('<', "\\<");
+        let _ = write!(out, "\n### {title}\n\n| operation |");
+        for library in &table.libraries {
+            let _ = write!(out, " {library} |");
+        }
+        out.push_str("\n|---|");
+        out.push_str(&"---:|".repeat(table.libraries.len()));
+        out.push('\n');
+        for ((_, label), means) in &table.rows {
+            let (cells, winner) = cells(&table, means, baseline);
+            let _ = write!(out, "| {label} |");
+            for (library, cell) in table.libraries.iter().zip(cells) {
+                match cell {
+                    Some(cell) if winner == Some(*library) => {
+                        let _ = write!(out, " **{cell}** |");
+                    }
+                    Some(cell) => {
+                        let _ = write!(out, " {cell} |");
+                    }
+                    None => out.push_str(" – |"),
+                }
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub fn render_html(records: &[BenchRecord], baseline: &str) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>ssz-arena leaderboard</title>\n\
+         <style>\n\
+         table { border-collapse: collapse; margin-bottom: 2em; }\n\
+         th, td { border: 1px solid #ccc; padding: 4px 8px; }\n\
+         td.time { text-align: right; }\n\
+         td.winner { font-weight: bold; background: #e6f4ea; }\n\
+         </style>\n</head>\n<body>\n<h2>Leaderboard</h2>\n",
+    );
+    let _ = writeln!(out, "<p>{}</p>", escape_html(&header(records, baseline)));
+    for table in tables(records) {
+        let _ = writeln!(out, "<h3>{}</h3>\n<table>", escape_html(&table.title));
+        out.push_str("<tr><th>operation</th>");
+        for library in &table.libraries {
+            let _ = write!(out, "<th>{}</th>", escape_html(library));
+        }
+        out.push_str("</tr>\n");
+        for ((_, label), means) in &table.rows {
+            let (cells, winner) = cells(&table, means, baseline);
+            let _ = write!(out, "<tr><td>{}</td>", escape_html(label));
+            for (library, cell) in table.libraries.iter().zip(cells) {
+                let class = if winner == Some(*library) {
+                    "time winner"
+                } else {
+                    "time"
+                };
+                let _ = write!(
+                    out,
+                    "<td class=\"{class}\">{}</td>",
+                    cell.as_deref().map_or_else(|| "–".to_string(), escape_html)
+                );
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+pub fn render_report(records: &[BenchRecord], baseline: &str, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => render_markdown(records, baseline),
+        ReportFormat::Html => render_html(records, baseline),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        library: &str,
+        object: &str,
+        operation: &str,
+        input: &str,
+        mean_ns: f64,
+    ) -> BenchRecord {
+        BenchRecord {
+            source: "criterion".to_string(),
+            library: library.to_string(),
+            object: object.to_string(),
+            operation: operation.to_string(),
+            input: input.to_string(),
+            git_rev: "0123456789ab".to_string(),
+            sszb_rev: None,
+            id: format!("{object}/{library}/{operation}"),
+            mean_ns,
+            mean_lower_ns: None,
+            mean_upper_ns: None,
+            median_ns: mean_ns,
+            stddev_ns: None,
+            samples: None,
+            throughput_bytes: None,
+            bytes_per_sec: None,
+            alloc_count: None,
+            alloc_bytes: None,
+        }
+    }
+
+    fn block(library: &str, operation: &str, mean_ns: f64) -> BenchRecord {
+        record(
+            library,
+            "SignedBeaconBlock",
+            operation,
+            "mainnet/block/00",
+            mean_ns,
+        )
+    }
+
+    #[test]
+    fn tables_follow_object_order_then_alphabetical() {
+        let records: Vec<_> = [
+            "Zeta",
+            "BeaconState",
+            "Alpha",
+            "List<u64>",
+            "SignedBeaconBlock",
+        ]
+        .into_iter()
+        .map(|object| record("Sszb", object, "decode", "synthetic", 1.0))
+        .collect();
+        let titles: Vec<_> = tables(&records)
+            .into_iter()
+            .map(|table| table.title)
+            .collect();
+        assert_eq!(
+            titles,
+            [
+                "List<u64>",
+                "SignedBeaconBlock",
+                "BeaconState",
+                "Alpha",
+                "Zeta"
+            ]
+        );
+    }
+
+    #[test]
+    fn rows_and_libraries_in_order() {
+        let records = [
+            block("Grandine", "hash_tree_root", 3.0),
+            block("Lighthouse", "encode", 2.0),
+            block("Sszb", "decode", 1.0),
+        ];
+        let tables = tables(&records);
+        assert_eq!(tables[0].libraries, ["Sszb", "Lighthouse", "Grandine"]);
+        let rows: Vec<_> = tables[0]
+            .rows
+            .keys()
+            .map(|(_, label)| label.as_str())
+            .collect();
+        assert_eq!(rows, ["decode", "encode", "hash_tree_root"]);
+    }
+
+    #[test]
+    fn speedup_against_baseline() {
+        let records = [
+            block("Sszb", "decode", 50.0),
+            block("Lighthouse", "decode", 100.0),
+        ];
+        let markdown = render_markdown(&records, "Lighthouse");
+        assert!(
+            markdown.contains("| decode | **50.0 ns (2.00x)** | 100 ns (1.00x) |"),
+            "{markdown}"
+        );
+        // no speedup where the baseline wasn't measured
+        let markdown = render_markdown(&records, "Grandine");
+        assert!(
+            markdown.contains("| decode | **50.0 ns** | 100 ns |"),
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn winner_needs_two_libraries() {
+        let records = [block("Sszb", "decode", 50.0)];
+        let markdown = render_markdown(&records, "Lighthouse");
+        assert!(markdown.contains("| decode | 50.0 ns |"), "{markdown}");
+        assert!(!markdown.contains("**"), "{markdown}");
+        let html = render_html(&records, "Lighthouse");
+        assert!(html.contains("<td class=\"time\">50.0 ns</td>"), "{html}");
+        assert!(!html.contains("class=\"time winner\""), "{html}");
+    }
+
+    #[test]
+    fn missing_library_is_a_dash() {
+        let records = [
+            block("Sszb", "decode", 50.0),
+            block("Sszb", "encode", 20.0),
+            block("Lighthouse", "decode", 100.0),
+        ];
+        let markdown = render_markdown(&records, "Lighthouse");
+        assert!(markdown.contains("| encode | 20.0 ns | – |"), "{markdown}");
+        let html = render_html(&records, "Lighthouse");
+        assert!(
+            html.contains(
+                "<tr><td>encode</td><td class=\"time\">20.0 ns</td><td class=\"time\">–</td></tr>"
+            ),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn titles_are_escaped() {
+        let records = [record("Sszb", "List<u64>", "decode", "synthetic", 1.0)];
+        let markdown = render_markdown(&records, "Lighthouse");
+        assert!(markdown.contains("\n### List\\<u64>\n"), "{markdown}");
+        let html = render_html(&records, "Lighthouse");
+        assert!(html.contains("<h3>List&lt;u64&gt;</h3>"), "{html}");
+    }
+
+    #[test]
+    fn non_mainnet_inputs_name_their_network() {
+        let records = [
+            block("Sszb", "decode", 1.0),
+            record(
+                "Sszb",
+                "SignedBeaconBlock",
+                "decode",
+                "holesky/block/00",
+                1.0,
+            ),
+            record("Sszb", "List<u64>", "decode", "synthetic", 1.0),
+        ];
+        let titles: Vec<_> = tables(&records)
+            .into_iter()
+            .map(|table| table.title)
+            .collect();
+        assert_eq!(
+            titles,
+            [
+                "List<u64>",
+                "SignedBeaconBlock",
+                "SignedBeaconBlock (holesky)"
+            ]
+        );
+    }
+}
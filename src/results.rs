@@ -0,0 +1,750 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{fixture_digest, FixtureKind, Network};
+
+pub const DEFAULT_CRITERION_DIR: &str = "target/criterion";
+pub const DEFAULT_RESULTS_DIR: &str = "target/arena-results";
+/// Where `collect` looks for divan's output unless told otherwise, e.g. from
+/// `cargo bench --bench divan | tee target/arena-results/divan.txt`.
+pub const DEFAULT_DIVAN_OUTPUT: &str = "target/arena-results/divan.txt";
+
+/// One benchmark measurement, keyed by (library, object, operation, input, git rev).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    /// `criterion` for `benches/wall_time.rs`, `divan` for `benches/divan.rs`.
+    pub source: String,
+    pub library: String,
+    pub object: String,
+    pub operation: String,
+    /// `<network>/<kind>/<sha256>` of the fixture for block and state
+    /// benches, `synthetic` for inputs the bench builds itself.
+    pub input: String,
+    pub git_rev: String,
+    /// The sszb git dependency's revision from `Cargo.lock`.
+    pub sszb_rev: Option<String>,
+    /// The benchmark's own id, e.g. `SignedBeaconBlock/Sszb/decode`.
+    pub id: String,
+    pub mean_ns: f64,
+    /// 95% confidence interval of the mean, criterion only.
+    pub mean_lower_ns: Option<f64>,
+    pub mean_upper_ns: Option<f64>,
+    pub median_ns: f64,
+    pub stddev_ns: Option<f64>,
+    pub samples: Option<u64>,
+    /// Input bytes processed per iteration.
+    pub throughput_bytes: Option<u64>,
+    pub bytes_per_sec: Option<f64>,
+    /// Allocations and bytes allocated per iteration, divan only.
+    pub alloc_count: Option<u64>,
+    pub alloc_bytes: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum ResultsError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        reason: String,
+    },
+    /// Neither criterion nor divan results were found.
+    NoResults,
+}
+
+impl ResultsError {
+    fn io(path: &Path, source: std::io::Error) -> Self {
+        Self::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    fn parse(path: &Path, reason: impl fmt::Display) -> Self {
+        Self::Parse {
+            path: path.to_path_buf(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ResultsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::Parse { path, reason } => {
+                write!(f, "failed to parse {}: {reason}", path.display())
+            }
+            Self::NoResults => write!(
+                f,
+                "no criterion or divan results found, run `cargo bench` first"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResultsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The revisions every record of one collection run is tagged with.
+#[derive(Clone, Debug)]
+pub struct Revisions {
+    pub git_rev: String,
+    pub sszb_rev: Option<String>,
+}
+
+impl Revisions {
+    /// `HEAD` of the arena checkout, with `-dirty` for uncommitted changes, and
+    /// sszb's locked revision.
+    pub fn current() -> Self {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        };
+        let git_rev = match git(&["rev-parse", "--short=12", "HEAD"]) {
+            Some(rev) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+                format!("{rev}-dirty")
+            }
+            Some(rev) => rev,
+            None => "unknown".to_string(),
+        };
+        let sszb_rev = std::fs::read_to_string("Cargo.lock")
+            .ok()
+            .and_then(|lock| locked_git_rev(&lock, "sszb"));
+        Self { git_rev, sszb_rev }
+    }
+}
+
+/// The `#<rev>` of a git dependency's `source` line in `Cargo.lock`.
+fn locked_git_rev(lock: &str, package: &str) -> Option<String> {
+    let name = format!("name = \"{package}\"");
+    let mut lines = lock.lines().skip_while(|line| line.trim() != name);
+    lines.next()?;
+    lines
+        .take_while(|line| !line.starts_with("[[package]]"))
+        .find_map(|line| line.trim().strip_prefix("source = \"git+"))
+        .and_then(|source| source.trim_end_matches('"').rsplit_once('#'))
+        .map(|(_, rev)| rev.to_string())
+}
+
+/// Fingerprint of a benchmark input, see [`BenchRecord::input`].
+fn input_fingerprint(object: &str, network: Network) -> String {
+    let kind = match object {
+        "SignedBeaconBlock" | "Transactions (block)" => FixtureKind::Block,
+        "BeaconState" => FixtureKind::State,
+        _ => return "synthetic".to_string(),
+    };
+    let digest = fixture_digest(kind, network)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| "unknown".to_string());
+    format!("{network}/{}/{digest}", kind.as_str())
+}
+
+/// Criterion group names without the library, network or measurement, so
+/// the same object lines up across groups and with divan.
+fn criterion_object(group: &str) -> String {
+    let group = group.strip_suffix(" tree_hash").unwrap_or(group);
+    let group = group
+        .strip_prefix("Milhouse ")
+        .or_else(|| group.strip_prefix("Ghilhouse "))
+        .unwrap_or(group);
+    match group {
+        "List" => "List<u64>".to_string(),
+        "Transactions conversion" => "Transactions".to_string(),
+        // the fixture block's payload, not the synthetic list, and both time `TxOpaque/decode`
+        "Block transactions" => "Transactions (block)".to_string(),
+        group => group.to_string(),
+    }
+}
+
+fn library_name(library: &str) -> String {
+    match library {
+        "sszb" | "Sszb" => "Sszb".to_string(),
+        "sigp" | "Sigp" | "Lighthouse" => "Lighthouse".to_string(),
+        "grandine" | "Grandine" => "Grandine".to_string(),
+        library => library.to_string(),
+    }
+}
+
+#[derive(Deserialize)]
+struct CriterionBenchmark {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+    full_id: String,
+    throughput: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimate {
+    point_estimate: f64,
+    confidence_interval: CriterionInterval,
+}
+
+#[derive(Deserialize)]
+struct CriterionInterval {
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimates {
+    mean: CriterionEstimate,
+    median: CriterionEstimate,
+    std_dev: CriterionEstimate,
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, ResultsError> {
+    let text = std::fs::read_to_string(path).map_err(|e| ResultsError::io(path, e))?;
+    serde_json::from_str(&text).map_err(|e| ResultsError::parse(path, e))
+}
+
+/// Every `new/` measurement criterion left under `dir`, which is usually
+/// `target/criterion`. An absent directory yields no records.
+pub fn collect_criterion(dir: &Path, revs: &Revisions) -> Result<Vec<BenchRecord>, ResultsError> {
+    let mut new_dirs = Vec::new();
+    find_new_dirs(dir, &mut new_dirs)?;
+    new_dirs.sort();
+
+    let mut records = Vec::new();
+    for new_dir in new_dirs {
+        let benchmark: CriterionBenchmark = read_json(&new_dir.join("benchmark.json"))?;
+        let estimates: CriterionEstimates = read_json(&new_dir.join("estimates.json"))?;
+
+        // `group_name` appends the network for everything but mainnet
+        let (group, network) = match benchmark.group_id.rsplit_once('/') {
+            Some((group, network)) => match network.parse() {
+                Ok(network) => (group, network),
+                Err(_) => (benchmark.group_id.as_str(), Network::Mainnet),
+            },
+            None => (benchmark.group_id.as_str(), Network::Mainnet),
+        };
+        let object = criterion_object(group);
+        let throughput_bytes = benchmark
+            .throughput
+            .as_ref()
+            .and_then(|throughput| throughput.get("Bytes"))
+            .and_then(serde_json::Value::as_u64);
+        let mean_ns = estimates.mean.point_estimate;
+
+        records.push(BenchRecord {
+            source: "criterion".to_string(),
+            library: library_name(benchmark.function_id.as_deref().unwrap_or(group)),
+            operation: benchmark.value_str.unwrap_or_default(),
+            input: input_fingerprint(&object, network),
+            object,
+            git_rev: revs.git_rev.clone(),
+            sszb_rev: revs.sszb_rev.clone(),
+            id: benchmark.full_id,
+            mean_ns,
+            mean_lower_ns: Some(estimates.mean.confidence_interval.lower_bound),
+            mean_upper_ns: Some(estimates.mean.confidence_interval.upper_bound),
+            median_ns: estimates.median.point_estimate,
+            stddev_ns: Some(estimates.std_dev.point_estimate),
+            samples: None,
+            throughput_bytes,
+            bytes_per_sec: throughput_bytes.map(|bytes| bytes as f64 / (mean_ns * 1e-9)),
+            alloc_count: None,
+            alloc_bytes: None,
+        });
+    }
+    Ok(records)
+}
+
+fn find_new_dirs(dir: &Path, found: &mut Vec<PathBuf>) -> Result<(), ResultsError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(ResultsError::io(dir, e)),
+    };
+    for entry in entries {
+        let path = entry.map_err(|e| ResultsError::io(dir, e))?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "new") {
+            if path.join("estimates.json").is_file() {
+                found.push(path);
+            }
+        } else {
+            find_new_dirs(&path, found)?;
+        }
+    }
+    Ok(())
+}
+
+/// (library, object, operation) of a `benches/divan.rs` path such as
+/// `["sszb", "decode_beacon_block"]` or `["grandine", "decode_grandine_beacon_state"]`.
+fn divan_ids(path: &[String]) -> (String, String, String) {
+    let group = path.first().map(String::as_str).unwrap_or_default();
+    let name = path.last().map(String::as_str).unwrap_or_default();
+
+    let library = if name.ends_with("_sszb") {
+        "sszb"
+    } else if name.contains("sigp") {
+        "sigp"
+    } else if name.contains("grandine") {
+        "grandine"
+    } else {
+        group
+    };
+    let object = if name.contains("beacon_block") {
+        "SignedBeaconBlock"
+    } else if name.contains("beacon_state") {
+        "BeaconState"
+    } else if group == "exec_payload_header_list" {
+        "ExecutionPayloadHeader List"
+    } else if name.contains("list") {
+        "List<u64>"
+    } else {
+        name
+    };
+    let mut operation = name.split('_').next().unwrap_or(name).to_string();
+    for variant in ["fast", "naive"] {
+        if name.ends_with(&format!("_{variant}")) {
+            operation = format!("{operation} {variant}");
+        }
+    }
+    (library_name(library), object.to_string(), operation)
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // CSI sequences end at their first letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `85.2 µs` in nanoseconds.
+fn parse_duration_ns(text: &str) -> Option<f64> {
+    let (value, unit) = text.trim().split_once(' ')?;
+    let scale = match unit.trim() {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(value.parse::<f64>().ok()? * scale)
+}
+
+/// `504 KB` or `1.2 MiB` in bytes.
+fn parse_bytes(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let (value, unit) = text.split_once(' ').unwrap_or((text, "B"));
+    let scale = match unit.trim() {
+        "B" => 1.0,
+        "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((value.parse::<f64>().ok()? * scale).round() as u64)
+}
+
+/// Parses divan's tree output, as printed by `cargo bench --bench divan`.
+///
+/// Columns are split at the `│` positions of the header row so the tree's
+/// own `│` guides in the first column are left alone. Allocation counts come
+/// from the `alloc:` rows `AllocProfiler` prints under each benchmark.
+pub fn parse_divan(
+    output: &str,
+    path: &Path,
+    revs: &Revisions,
+) -> Result<Vec<BenchRecord>, ResultsError> {
+    let lines: Vec<Vec<char>> = output
+        .lines()
+        .map(|line| strip_ansi(line).chars().collect())
+        .collect();
+    let Some(header) = lines.iter().position(|line| {
+        let line: String = line.iter().collect();
+        line.contains("fastest") && line.contains("mean")
+    }) else {
+        return Err(ResultsError::parse(path, "no divan header row"));
+    };
+    let separators: Vec<usize> = lines[header]
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| **c == '│')
+        .map(|(i, _)| i)
+        .collect();
+    // fastest │ slowest │ median │ mean │ samples │ iters
+    if separators.len() < 5 {
+        return Err(ResultsError::parse(
+            path,
+            "divan header has too few columns",
+        ));
+    }
+
+    let mut records: Vec<BenchRecord> = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    // the allocation section being read and how many of its rows were seen
+    let mut section: Option<(String, usize)> = None;
+
+    for line in &lines[header + 1..] {
+        let column = |i: usize| -> String {
+            let start = if i == 0 { 0 } else { separators[i - 1] + 1 };
+            let end = separators.get(i).copied().unwrap_or(line.len());
+            line.get(start.min(line.len())..end.min(line.len()))
+                .map(|chars| chars.iter().collect::<String>())
+                .unwrap_or_default()
+                // the first column's indentation is the tree depth
+                .trim_end()
+                .trim_start_matches(|c| i > 0 && c == ' ')
+                .to_string()
+        };
+        let first = column(0);
+        let prefix_len = first
+            .chars()
+            .take_while(|c| matches!(c, '│' | '├' | '╰' | '─' | ' '))
+            .count();
+        let rest: String = first.chars().skip(prefix_len).collect();
+        if rest.is_empty() {
+            continue;
+        }
+
+        if rest.ends_with(':') {
+            section = Some((rest.trim_end_matches(':').to_string(), 0));
+            continue;
+        }
+        if let Some((name, row)) = &mut section {
+            // each section is a count row followed by a bytes row
+            if name == "alloc" {
+                if let Some(record) = records.last_mut() {
+                    let mean = column(3);
+                    match row {
+                        // means over the samples can be fractional
+                        0 => {
+                            record.alloc_count =
+                                mean.parse::<f64>().ok().map(|count| count.round() as u64)
+                        }
+                        _ => record.alloc_bytes = parse_bytes(&mean),
+                    }
+                }
+            }
+            *row += 1;
+            if *row == 2 {
+                section = None;
+            }
+            continue;
+        }
+
+        let (name, fastest) = rest.split_once(' ').unwrap_or((&rest, ""));
+        let depth = prefix_len / 3;
+        stack.truncate(depth.saturating_sub(1));
+        stack.push(name.to_string());
+
+        let (Some(median_ns), Some(mean_ns)) =
+            (parse_duration_ns(&column(2)), parse_duration_ns(&column(3)))
+        else {
+            // a group row, or a benchmark whose first column wrapped
+            if parse_duration_ns(fastest).is_none() {
+                continue;
+            }
+            return Err(ResultsError::parse(
+                path,
+                format!("unreadable divan row for {}", stack.join("::")),
+            ));
+        };
+        let (library, object, operation) = divan_ids(&stack);
+        records.push(BenchRecord {
+            source: "divan".to_string(),
+            library,
+            operation,
+            // divan.rs always loads and decodes mainnet fixtures
+            input: input_fingerprint(&object, Network::Mainnet),
+            object,
+            git_rev: revs.git_rev.clone(),
+            sszb_rev: revs.sszb_rev.clone(),
+            id: stack.join("::"),
+            mean_ns,
+            mean_lower_ns: None,
+            mean_upper_ns: None,
+            median_ns,
+            stddev_ns: None,
+            samples: column(4).parse().ok(),
+            throughput_bytes: None,
+            bytes_per_sec: None,
+            alloc_count: None,
+            alloc_bytes: None,
+        });
+    }
+    Ok(records)
+}
+
+pub fn collect_divan(path: &Path, revs: &Revisions) -> Result<Vec<BenchRecord>, ResultsError> {
+    let output = std::fs::read_to_string(path).map_err(|e| ResultsError::io(path, e))?;
+    parse_divan(&output, path, revs)
+}
+
+const CSV_HEADER: &str = "source,library,object,operation,input,git_rev,sszb_rev,id,mean_ns,mean_lower_ns,mean_upper_ns,median_ns,stddev_ns,samples,throughput_bytes,bytes_per_sec,alloc_count,alloc_bytes";
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv_opt<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+pub fn to_csv(records: &[BenchRecord]) -> String {
+    let mut csv = format!("{CSV_HEADER}\n");
+    for r in records {
+        let fields = [
+            csv_field(&r.source),
+            csv_field(&r.library),
+            csv_field(&r.object),
+            csv_field(&r.operation),
+            csv_field(&r.input),
+            csv_field(&r.git_rev),
+            csv_field(r.sszb_rev.as_deref().unwrap_or_default()),
+            csv_field(&r.id),
+            r.mean_ns.to_string(),
+            csv_opt(r.mean_lower_ns),
+            csv_opt(r.mean_upper_ns),
+            r.median_ns.to_string(),
+            csv_opt(r.stddev_ns),
+            csv_opt(r.samples),
+            csv_opt(r.throughput_bytes),
+            csv_opt(r.bytes_per_sec),
+            csv_opt(r.alloc_count),
+            csv_opt(r.alloc_bytes),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Writes `results.json` and `results.csv` into `dir`, returning their paths.
+pub fn write_results(
+    dir: &Path,
+    records: &[BenchRecord],
+) -> Result<(PathBuf, PathBuf), ResultsError> {
+    std::fs::create_dir_all(dir).map_err(|e| ResultsError::io(dir, e))?;
+    let json_path = dir.join("results.json");
+    let json =
+        serde_json::to_string_pretty(records).map_err(|e| ResultsError::parse(&json_path, e))?;
+    std::fs::write(&json_path, json).map_err(|e| ResultsError::io(&json_path, e))?;
+    let csv_path = dir.join("results.csv");
+    std::fs::write(&csv_path, to_csv(records)).map_err(|e| ResultsError::io(&csv_path, e))?;
+    Ok((json_path, csv_path))
+}
+
+/// Reads a `results.json` written by [`write_results`].
+pub fn read_results(path: &Path) -> Result<Vec<BenchRecord>, ResultsError> {
+    read_json(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A few benchmarks laid out as `cargo bench --bench divan` prints them,
+    /// with the `AllocProfiler` sections under each.
+    const DIVAN_OUTPUT: &str = "\
+Timer precision: 41 ns
+divan                          fastest        │ slowest       │ median        │ mean          │ samples │ iters
+├─ exec_payload_header_list                   │               │               │               │         │
+│  ╰─ decode_list_sszb         1.208 ms       │ 2.511 ms      │ 1.25 ms       │ 1.301 ms      │ 100     │ 100
+│                              max alloc:     │               │               │               │         │
+│                                3            │ 3             │ 3             │ 3             │
+│                                1.2 MB       │ 1.2 MB        │ 1.2 MB        │ 1.2 MB        │
+│                              alloc:         │               │               │               │         │
+│                                4            │ 4             │ 4             │ 4             │
+│                                1.5 MB       │ 1.5 MB        │ 1.5 MB        │ 1.5 MB        │
+╰─ sszb                                       │               │               │               │         │
+   ├─ decode_beacon_block      85.2 µs        │ 140.1 µs      │ 86.9 µs       │ 88.45 µs      │ 100     │ 100
+   │                           alloc:         │               │               │               │         │
+   │                             1221         │ 1221          │ 1221          │ 1220.5        │
+   │                             504 KB       │ 504 KB        │ 504 KB        │ 504 KB        │
+   ╰─ decode_list              12.04 µs       │ 30.5 µs       │ 12.3 µs       │ 12.61 µs      │ 100     │ 800
+";
+
+    fn revs() -> Revisions {
+        Revisions {
+            git_rev: "0123456789ab".to_string(),
+            sszb_rev: Some("fedcba98".to_string()),
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= expected * 1e-9,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn parses_divan_tree() {
+        let records = parse_divan(DIVAN_OUTPUT, Path::new("divan.txt"), &revs()).unwrap();
+        let ids: Vec<&str> = records.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "exec_payload_header_list::decode_list_sszb",
+                "sszb::decode_beacon_block",
+                "sszb::decode_list",
+            ]
+        );
+
+        let list = &records[0];
+        assert_eq!(
+            (
+                list.library.as_str(),
+                list.object.as_str(),
+                list.operation.as_str()
+            ),
+            ("Sszb", "ExecutionPayloadHeader List", "decode")
+        );
+        assert_eq!(list.source, "divan");
+        assert_eq!(list.input, "synthetic");
+        assert_eq!(list.git_rev, "0123456789ab");
+        assert_eq!(list.sszb_rev.as_deref(), Some("fedcba98"));
+        assert_close(list.mean_ns, 1_301_000.0);
+        assert_close(list.median_ns, 1_250_000.0);
+        assert_eq!(list.samples, Some(100));
+        // the `alloc:` section, not `max alloc:` above it
+        assert_eq!(list.alloc_count, Some(4));
+        assert_eq!(list.alloc_bytes, Some(1_500_000));
+
+        let block = &records[1];
+        assert_eq!(
+            (
+                block.library.as_str(),
+                block.object.as_str(),
+                block.operation.as_str()
+            ),
+            ("Sszb", "SignedBeaconBlock", "decode")
+        );
+        assert!(block.input.starts_with("mainnet/block/"));
+        assert_close(block.mean_ns, 88_450.0);
+        assert_close(block.median_ns, 86_900.0);
+        assert_eq!(block.alloc_count, Some(1221));
+        assert_eq!(block.alloc_bytes, Some(504_000));
+
+        let list = &records[2];
+        assert_eq!(list.object, "List<u64>");
+        assert_close(list.mean_ns, 12_610.0);
+        assert_eq!(list.alloc_count, None);
+        assert_eq!(list.alloc_bytes, None);
+    }
+
+    #[test]
+    fn divan_output_needs_a_header() {
+        assert!(parse_divan("Timer precision: 41 ns\n", Path::new("divan.txt"), &revs()).is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_close(parse_duration_ns("85.2 µs").unwrap(), 85_200.0);
+        assert_close(parse_duration_ns("85.2 us").unwrap(), 85_200.0);
+        assert_close(parse_duration_ns(" 1.5 ms ").unwrap(), 1_500_000.0);
+        assert_close(parse_duration_ns("12 ns").unwrap(), 12.0);
+        assert_close(parse_duration_ns("640 ps").unwrap(), 0.64);
+        assert_close(parse_duration_ns("2 s").unwrap(), 2e9);
+        assert_eq!(parse_duration_ns("3 min"), None);
+        assert_eq!(parse_duration_ns("85.2"), None);
+        assert_eq!(parse_duration_ns(""), None);
+    }
+
+    #[test]
+    fn parses_byte_sizes() {
+        assert_eq!(parse_bytes("504 KB"), Some(504_000));
+        assert_eq!(parse_bytes("1.5 MB"), Some(1_500_000));
+        assert_eq!(parse_bytes("2 GB"), Some(2_000_000_000));
+        assert_eq!(parse_bytes("1 KiB"), Some(1024));
+        assert_eq!(parse_bytes("1.2 MiB"), Some(1_258_291));
+        assert_eq!(parse_bytes("1 GiB"), Some(1 << 30));
+        assert_eq!(parse_bytes("68 B"), Some(68));
+        assert_eq!(parse_bytes("68"), Some(68));
+        assert_eq!(parse_bytes("2 PB"), None);
+        assert_eq!(parse_bytes("lots"), None);
+    }
+
+    #[test]
+    fn reads_locked_git_revision() {
+        let lock = r#"# This file is automatically @generated by Cargo.
+version = 3
+
+[[package]]
+name = "serde"
+version = "1.0.210"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "sszb"
+version = "0.1.0"
+source = "git+https://github.com/ghiliweld/sszb.git#5f2c1e0d9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d"
+dependencies = [
+ "sszb_derive",
+]
+
+[[package]]
+name = "sszb_derive"
+version = "0.1.0"
+source = "git+https://github.com/ghiliweld/sszb.git?branch=main#0123abcd"
+"#;
+        assert_eq!(
+            locked_git_rev(lock, "sszb").as_deref(),
+            Some("5f2c1e0d9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d")
+        );
+        assert_eq!(
+            locked_git_rev(lock, "sszb_derive").as_deref(),
+            Some("0123abcd")
+        );
+        // registry packages have no revision, missing ones aren't found
+        assert_eq!(locked_git_rev(lock, "serde"), None);
+        assert_eq!(locked_git_rev(lock, "milhouse"), None);
+    }
+
+    #[test]
+    fn criterion_objects_keep_transaction_inputs_apart() {
+        assert_eq!(criterion_object("Transactions tree_hash"), "Transactions");
+        assert_eq!(criterion_object("Transactions conversion"), "Transactions");
+        assert_eq!(
+            criterion_object("Block transactions"),
+            "Transactions (block)"
+        );
+        assert_eq!(criterion_object("Milhouse List tree_hash"), "List<u64>");
+        assert_eq!(
+            input_fingerprint("Transactions", Network::Mainnet),
+            "synthetic"
+        );
+    }
+}
@@ -0,0 +1,973 @@
+use std::ops::Range;
+
+use alloy_primitives::B256;
+use sha2::{Digest, Sha256};
+use typenum::Unsigned;
+
+use crate::{ForkName, Preset};
+
+/// Just enough of an SSZ type's shape to walk its encoding, name the field a
+/// byte offset falls in and hash it the way the spec does.
+///
+/// Written out by hand from the spec rather than derived from the containers
+/// in `beacon_block.rs` and `beacon_state.rs`, so it stays an independent
+/// check on them; the tests below fail when the two drift apart.
+#[derive(Clone, Debug)]
+pub(crate) enum Schema {
+    /// Fixed-size leaf: uints, roots, keys, signatures, bitvectors, byte vectors.
+    Fixed(usize),
+    /// Byte list with its maximum length.
+    Bytes(usize),
+    /// Bitlist with its maximum length in bits.
+    Bits(usize),
+    Container(Vec<(&'static str, Schema)>),
+    Vector(Box<Schema>, usize),
+    /// List with its maximum number of elements.
+    List(Box<Schema>, usize),
+}
+
+const U8: Schema = Schema::Fixed(1);
+const U64: Schema = Schema::Fixed(8);
+const ROOT: Schema = Schema::Fixed(32);
+const ADDRESS: Schema = Schema::Fixed(20);
+const PUBKEY: Schema = Schema::Fixed(48);
+const SIGNATURE: Schema = Schema::Fixed(96);
+pub(crate) const OFFSET: usize = 4;
+
+const BYTES_PER_CHUNK: usize = 32;
+const HISTORICAL_ROOTS_LIMIT: usize = 1 << 24;
+const VALIDATOR_REGISTRY_LIMIT: usize = 1 << 40;
+const MAX_BYTES_PER_TRANSACTION: usize = 1 << 30;
+const MAX_TRANSACTIONS_PER_PAYLOAD: usize = 1 << 20;
+const MAX_VALIDATORS_PER_COMMITTEE: usize = 2048;
+const PENDING_DEPOSITS_LIMIT: usize = 1 << 27;
+
+/// Where one field of a container sits in its encoding.
+pub(crate) struct FieldLayout<'a> {
+    pub(crate) name: &'static str,
+    pub(crate) schema: &'a Schema,
+    /// Position of the field's offset, for variable-size fields.
+    pub(crate) offset_at: Option<usize>,
+    pub(crate) range: Range<usize>,
+}
+
+impl Schema {
+    pub(crate) fn fixed_len(&self) -> Option<usize> {
+        match self {
+            Self::Fixed(len) => Some(*len),
+            Self::Bytes(_) | Self::Bits(_) | Self::List(..) => None,
+            Self::Container(fields) => fields.iter().map(|(_, field)| field.fixed_len()).sum(),
+            Self::Vector(elem, len) => elem.fixed_len().map(|elem| elem * len),
+        }
+    }
+
+    /// Dotted path to the field holding byte `pos` of `bytes`, e.g.
+    /// `.message.body.attestations[3].data.slot`. Stops at the deepest field
+    /// it can still make sense of, so malformed input yields a shorter path.
+    pub(crate) fn path_at(&self, bytes: &[u8], pos: usize) -> String {
+        let mut path = String::new();
+        self.walk(bytes, pos, &mut path);
+        path
+    }
+
+    /// Layout of a container's fields in `bytes`, `None` if this isn't a
+    /// container or its offsets point outside `bytes` or backwards.
+    pub(crate) fn fields(&self, bytes: &[u8]) -> Option<Vec<FieldLayout<'_>>> {
+        let Self::Container(fields) = self else {
+            return None;
+        };
+        let mut cursor = 0;
+        let mut layout = Vec::with_capacity(fields.len());
+        for (name, field) in fields {
+            let (offset_at, range) = match field.fixed_len() {
+                Some(len) => (None, cursor..cursor + len),
+                None => {
+                    let offset = read_offset(bytes, cursor)?;
+                    (Some(cursor), offset..offset)
+                }
+            };
+            cursor += if offset_at.is_some() {
+                OFFSET
+            } else {
+                range.len()
+            };
+            layout.push(FieldLayout {
+                name,
+                schema: field,
+                offset_at,
+                range,
+            });
+        }
+        // each variable-size field runs up to the next one's offset, the last to the end
+        let mut end = bytes.len();
+        for field in layout.iter_mut().rev() {
+            if field.offset_at.is_some() {
+                field.range.end = end;
+                end = field.range.start;
+            }
+        }
+        layout
+            .iter()
+            .all(|field| field.range.start <= field.range.end && field.range.end <= bytes.len())
+            .then_some(layout)
+    }
+
+    /// Range of element `index` of a list or vector in `bytes`.
+    pub(crate) fn element(&self, bytes: &[u8], index: usize) -> Option<(Range<usize>, &Schema)> {
+        let (Self::Vector(elem, _) | Self::List(elem, _)) = self else {
+            return None;
+        };
+        let range = match elem.fixed_len() {
+            Some(len) => index * len..(index + 1) * len,
+            None => {
+                let count = read_offset(bytes, 0)? / OFFSET;
+                if index >= count {
+                    return None;
+                }
+                let start = read_offset(bytes, index * OFFSET)?;
+                let end = if index + 1 < count {
+                    read_offset(bytes, (index + 1) * OFFSET)?
+                } else {
+                    bytes.len()
+                };
+                start..end
+            }
+        };
+        (range.start <= range.end && range.end <= bytes.len()).then_some((range, &**elem))
+    }
+
+    fn walk(&self, bytes: &[u8], pos: usize, path: &mut String) {
+        match self {
+            Self::Fixed(_) | Self::Bytes(_) | Self::Bits(_) => {}
+            Self::Container(fields) => walk_container(fields, bytes, pos, path),
+            Self::Vector(elem, _) | Self::List(elem, _) => walk_elements(elem, bytes, pos, path),
+        }
+    }
+
+    /// The spec's `hash_tree_root` of `bytes`, `None` if they don't fit the
+    /// schema or overrun a limit.
+    pub(crate) fn hash_tree_root(&self, bytes: &[u8]) -> Option<B256> {
+        let root = match self {
+            Self::Fixed(len) => {
+                if bytes.len() != *len {
+                    return None;
+                }
+                merkleize(pack(bytes), len.div_ceil(BYTES_PER_CHUNK))?
+            }
+            Self::Bytes(limit) => {
+                if bytes.len() > *limit {
+                    return None;
+                }
+                let root = merkleize(pack(bytes), limit.div_ceil(BYTES_PER_CHUNK))?;
+                mix_in_length(root, bytes.len())
+            }
+            Self::Bits(limit) => {
+                let (bits, len) = strip_delimiter(bytes)?;
+                if len > *limit {
+                    return None;
+                }
+                let root = merkleize(pack(&bits), limit.div_ceil(8 * BYTES_PER_CHUNK))?;
+                mix_in_length(root, len)
+            }
+            Self::Container(_) => {
+                let roots = self
+                    .field_roots(bytes)?
+                    .into_iter()
+                    .map(|(_, root)| root)
+                    .collect::<Vec<_>>();
+                let count = roots.len();
+                merkleize(roots, count)?
+            }
+            Self::Vector(elem, len) => {
+                let (roots, count) = self.element_chunks(elem, bytes)?;
+                if count != *len {
+                    return None;
+                }
+                merkleize(roots, chunk_limit(elem, *len))?
+            }
+            Self::List(elem, limit) => {
+                let (roots, count) = self.element_chunks(elem, bytes)?;
+                if count > *limit {
+                    return None;
+                }
+                mix_in_length(merkleize(roots, chunk_limit(elem, *limit))?, count)
+            }
+        };
+        Some(root)
+    }
+
+    /// Name and `hash_tree_root` of every field of a container, in order:
+    /// the leaves its own root is merkleized from.
+    pub(crate) fn field_roots(&self, bytes: &[u8]) -> Option<Vec<(&'static str, B256)>> {
+        let layout = self.fields(bytes)?;
+        // the first offset must point right past the fixed-size part
+        let fixed_end: usize = layout
+            .iter()
+            .map(|field| field.offset_at.map_or(field.range.len(), |_| OFFSET))
+            .sum();
+        let variable_start = layout
+            .iter()
+            .find(|field| field.offset_at.is_some())
+            .map_or(bytes.len(), |field| field.range.start);
+        if variable_start != fixed_end {
+            return None;
+        }
+        layout
+            .iter()
+            .map(|field| {
+                Some((
+                    field.name,
+                    field.schema.hash_tree_root(&bytes[field.range.clone()])?,
+                ))
+            })
+            .collect()
+    }
+
+    /// The schema and bytes of the field at `path` below this container.
+    pub(crate) fn descend<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        path: &[&str],
+    ) -> Option<(&'a Schema, &'a [u8])> {
+        let Some((name, rest)) = path.split_first() else {
+            return Some((self, bytes));
+        };
+        let field = self
+            .fields(bytes)?
+            .into_iter()
+            .find(|field| field.name == *name)?;
+        field.schema.descend(&bytes[field.range], rest)
+    }
+
+    /// Chunks a list or vector's elements merkleize from, and how many
+    /// elements there are. Uints and roots pack into the chunks directly,
+    /// anything else contributes its own root.
+    fn element_chunks(&self, elem: &Schema, bytes: &[u8]) -> Option<(Vec<B256>, usize)> {
+        if let Some(len) = packed_len(elem) {
+            if bytes.len() % len != 0 {
+                return None;
+            }
+            return Some((pack(bytes), bytes.len() / len));
+        }
+        let count = match elem.fixed_len() {
+            Some(0) => return None,
+            Some(len) if bytes.len() % len != 0 => return None,
+            Some(len) => bytes.len() / len,
+            None if bytes.is_empty() => 0,
+            None => {
+                let first = read_offset(bytes, 0)?;
+                if first == 0 || first % OFFSET != 0 {
+                    return None;
+                }
+                first / OFFSET
+            }
+        };
+        let roots = (0..count)
+            .map(|index| {
+                let (range, elem) = self.element(bytes, index)?;
+                elem.hash_tree_root(&bytes[range])
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((roots, count))
+    }
+}
+
+/// Element size of a basic type that packs into chunks, `None` for
+/// composite elements. Byte vectors of up to 32 bytes pack the same way as
+/// their own root, so roots count as basic here too.
+fn packed_len(elem: &Schema) -> Option<usize> {
+    match elem {
+        Schema::Fixed(len) if *len <= BYTES_PER_CHUNK && BYTES_PER_CHUNK % len == 0 => Some(*len),
+        _ => None,
+    }
+}
+
+fn chunk_limit(elem: &Schema, limit: usize) -> usize {
+    match packed_len(elem) {
+        Some(len) => (limit * len).div_ceil(BYTES_PER_CHUNK),
+        None => limit,
+    }
+}
+
+fn pack(bytes: &[u8]) -> Vec<B256> {
+    bytes
+        .chunks(BYTES_PER_CHUNK)
+        .map(|chunk| {
+            let mut padded = B256::ZERO;
+            padded[..chunk.len()].copy_from_slice(chunk);
+            padded
+        })
+        .collect()
+}
+
+fn hash_pair(left: &B256, right: &B256) -> B256 {
+    B256::from_slice(
+        &Sha256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize(),
+    )
+}
+
+/// Merkle root of `chunks` padded with zero chunks to the next power of two
+/// of `limit`, `None` if there are more chunks than `limit`.
+fn merkleize(mut chunks: Vec<B256>, limit: usize) -> Option<B256> {
+    if chunks.len() > limit.max(1) {
+        return None;
+    }
+    let mut zero = B256::ZERO;
+    for _ in 0..limit.max(1).next_power_of_two().trailing_zeros() {
+        if chunks.len() % 2 == 1 {
+            chunks.push(zero);
+        }
+        chunks = chunks
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        zero = hash_pair(&zero, &zero);
+    }
+    Some(chunks.first().copied().unwrap_or(zero))
+}
+
+fn mix_in_length(root: B256, len: usize) -> B256 {
+    let mut length = B256::ZERO;
+    length[..8].copy_from_slice(&(len as u64).to_le_bytes());
+    hash_pair(&root, &length)
+}
+
+/// A bitlist's bits without the delimiter bit, and how many there are.
+fn strip_delimiter(bytes: &[u8]) -> Option<(Vec<u8>, usize)> {
+    let (&last, _) = bytes.split_last()?;
+    if last == 0 {
+        return None;
+    }
+    let high = 7 - last.leading_zeros() as usize;
+    let len = (bytes.len() - 1) * 8 + high;
+    let mut bits = bytes.to_vec();
+    *bits.last_mut()? &= !(1 << high);
+    bits.truncate(len.div_ceil(8));
+    Some((bits, len))
+}
+
+fn read_offset(bytes: &[u8], at: usize) -> Option<usize> {
+    let offset = bytes.get(at..at + OFFSET)?;
+    Some(u32::from_le_bytes(offset.try_into().ok()?) as usize)
+}
+
+fn walk_container(fields: &[(&'static str, Schema)], bytes: &[u8], pos: usize, path: &mut String) {
+    let mut cursor = 0;
+    // (name, schema, offset) of every variable-size field, in order
+    let mut variable = Vec::new();
+    for (name, field) in fields {
+        let len = match field.fixed_len() {
+            Some(len) => len,
+            None => {
+                let Some(offset) = read_offset(bytes, cursor) else {
+                    return;
+                };
+                variable.push((*name, field, offset));
+                OFFSET
+            }
+        };
+        if (cursor..cursor + len).contains(&pos) {
+            path.push('.');
+            path.push_str(name);
+            if field.fixed_len().is_some() {
+                if let Some(field_bytes) = bytes.get(cursor..cursor + len) {
+                    field.walk(field_bytes, pos - cursor, path);
+                }
+            }
+            return;
+        }
+        cursor += len;
+    }
+    for (i, (name, field, start)) in variable.iter().enumerate() {
+        let end = variable
+            .get(i + 1)
+            .map_or(bytes.len(), |(_, _, next)| *next);
+        if (*start..end).contains(&pos) {
+            path.push('.');
+            path.push_str(name);
+            if let Some(field_bytes) = bytes.get(*start..end) {
+                field.walk(field_bytes, pos - start, path);
+            }
+            return;
+        }
+    }
+}
+
+fn walk_elements(elem: &Schema, bytes: &[u8], pos: usize, path: &mut String) {
+    if let Some(len) = elem.fixed_len() {
+        if len == 0 {
+            return;
+        }
+        let index = pos / len;
+        path.push_str(&format!("[{index}]"));
+        if let Some(elem_bytes) = bytes.get(index * len..(index + 1) * len) {
+            elem.walk(elem_bytes, pos - index * len, path);
+        }
+        return;
+    }
+    let Some(first) = read_offset(bytes, 0) else {
+        return;
+    };
+    let count = first / OFFSET;
+    if pos < first {
+        // inside the offset table itself
+        path.push_str(&format!("[{}]", pos / OFFSET));
+        return;
+    }
+    for index in 0..count {
+        let Some(start) = read_offset(bytes, index * OFFSET) else {
+            return;
+        };
+        let end = if index + 1 < count {
+            let Some(end) = read_offset(bytes, (index + 1) * OFFSET) else {
+                return;
+            };
+            end
+        } else {
+            bytes.len()
+        };
+        if (start..end).contains(&pos) {
+            path.push_str(&format!("[{index}]"));
+            if let Some(elem_bytes) = bytes.get(start..end) {
+                elem.walk(elem_bytes, pos - start, path);
+            }
+            return;
+        }
+    }
+}
+
+fn container(fields: Vec<(&'static str, Schema)>) -> Schema {
+    Schema::Container(fields)
+}
+
+fn list(elem: Schema, limit: usize) -> Schema {
+    Schema::List(Box::new(elem), limit)
+}
+
+fn vector(elem: Schema, len: usize) -> Schema {
+    Schema::Vector(Box::new(elem), len)
+}
+
+fn bitvector<N: Unsigned>() -> Schema {
+    Schema::Fixed(N::to_usize().div_ceil(8))
+}
+
+fn checkpoint() -> Schema {
+    container(vec![("epoch", U64), ("root", ROOT)])
+}
+
+fn eth1_data() -> Schema {
+    container(vec![
+        ("deposit_root", ROOT),
+        ("deposit_count", U64),
+        ("block_hash", ROOT),
+    ])
+}
+
+fn block_header() -> Schema {
+    container(vec![
+        ("slot", U64),
+        ("proposer_index", U64),
+        ("parent_root", ROOT),
+        ("state_root", ROOT),
+        ("body_root", ROOT),
+    ])
+}
+
+fn attestation_data() -> Schema {
+    container(vec![
+        ("slot", U64),
+        ("index", U64),
+        ("beacon_block_root", ROOT),
+        ("source", checkpoint()),
+        ("target", checkpoint()),
+    ])
+}
+
+fn execution_payload<P: Preset>(fork: ForkName, header: bool) -> Schema {
+    let mut fields = vec![
+        ("parent_hash", ROOT),
+        ("fee_recipient", ADDRESS),
+        ("state_root", ROOT),
+        ("receipts_root", ROOT),
+        ("logs_bloom", Schema::Fixed(256)),
+        ("prev_randao", ROOT),
+        ("block_number", U64),
+        ("gas_limit", U64),
+        ("gas_used", U64),
+        ("timestamp", U64),
+        ("extra_data", Schema::Bytes(32)),
+        ("base_fee_per_gas", ROOT),
+        ("block_hash", ROOT),
+    ];
+    if header {
+        fields.push(("transactions_root", ROOT));
+    } else {
+        fields.push((
+            "transactions",
+            list(
+                Schema::Bytes(MAX_BYTES_PER_TRANSACTION),
+                MAX_TRANSACTIONS_PER_PAYLOAD,
+            ),
+        ));
+    }
+    if fork >= ForkName::Capella && header {
+        fields.push(("withdrawals_root", ROOT));
+    } else if fork >= ForkName::Capella {
+        fields.push((
+            "withdrawals",
+            list(
+                container(vec![
+                    ("index", U64),
+                    ("validator_index", U64),
+                    ("address", ADDRESS),
+                    ("amount", U64),
+                ]),
+                P::MaxWithdrawalsPerPayload::to_usize(),
+            ),
+        ));
+    }
+    if fork >= ForkName::Deneb {
+        fields.push(("blob_gas_used", U64));
+        fields.push(("excess_blob_gas", U64));
+    }
+    container(fields)
+}
+
+fn sync_committee<P: Preset>() -> Schema {
+    container(vec![
+        ("pubkeys", vector(PUBKEY, P::SyncCommitteeSize::to_usize())),
+        ("aggregate_pubkey", PUBKEY),
+    ])
+}
+
+pub(crate) fn signed_beacon_block<P: Preset>(fork: ForkName) -> Schema {
+    let signed_header = container(vec![("message", block_header()), ("signature", SIGNATURE)]);
+    // Electra moved committee indices out of the aggregation bits and into
+    // `committee_bits`, widening the per-attestation limits
+    let (max_attesting, max_attester_slashings, max_attestations) = if fork >= ForkName::Electra {
+        (P::MaxValidatorsPerSlot::to_usize(), 1, 8)
+    } else {
+        (MAX_VALIDATORS_PER_COMMITTEE, 2, 128)
+    };
+    let indexed_attestation = container(vec![
+        ("attesting_indices", list(U64, max_attesting)),
+        ("data", attestation_data()),
+        ("signature", SIGNATURE),
+    ]);
+    let mut attestation = vec![
+        ("aggregation_bits", Schema::Bits(max_attesting)),
+        ("data", attestation_data()),
+        ("signature", SIGNATURE),
+    ];
+    if fork >= ForkName::Electra {
+        attestation.push(("committee_bits", bitvector::<P::MaxCommitteesPerSlot>()));
+    }
+
+    let mut body = vec![
+        ("randao_reveal", SIGNATURE),
+        ("eth1_data", eth1_data()),
+        ("graffiti", ROOT),
+        (
+            "proposer_slashings",
+            list(
+                container(vec![
+                    ("signed_header_1", signed_header.clone()),
+                    ("signed_header_2", signed_header),
+                ]),
+                16,
+            ),
+        ),
+        (
+            "attester_slashings",
+            list(
+                container(vec![
+                    ("attestation_1", indexed_attestation.clone()),
+                    ("attestation_2", indexed_attestation),
+                ]),
+                max_attester_slashings,
+            ),
+        ),
+        (
+            "attestations",
+            list(container(attestation), max_attestations),
+        ),
+        (
+            "deposits",
+            list(
+                container(vec![
+                    ("proof", vector(ROOT, 33)),
+                    (
+                        "data",
+                        container(vec![
+                            ("pubkey", PUBKEY),
+                            ("withdrawal_credentials", ROOT),
+                            ("amount", U64),
+                            ("signature", SIGNATURE),
+                        ]),
+                    ),
+                ]),
+                16,
+            ),
+        ),
+        (
+            "voluntary_exits",
+            list(
+                container(vec![
+                    (
+                        "message",
+                        container(vec![("epoch", U64), ("validator_index", U64)]),
+                    ),
+                    ("signature", SIGNATURE),
+                ]),
+                16,
+            ),
+        ),
+    ];
+    if fork >= ForkName::Altair {
+        body.push((
+            "sync_aggregate",
+            container(vec![
+                ("sync_committee_bits", bitvector::<P::SyncCommitteeSize>()),
+                ("sync_committee_signature", SIGNATURE),
+            ]),
+        ));
+    }
+    if fork >= ForkName::Bellatrix {
+        body.push(("execution_payload", execution_payload::<P>(fork, false)));
+    }
+    if fork >= ForkName::Capella {
+        body.push((
+            "bls_to_execution_changes",
+            list(
+                container(vec![
+                    (
+                        "message",
+                        container(vec![
+                            ("validator_index", U64),
+                            ("from_bls_pubkey", PUBKEY),
+                            ("to_execution_address", ADDRESS),
+                        ]),
+                    ),
+                    ("signature", SIGNATURE),
+                ]),
+                16,
+            ),
+        ));
+    }
+    if fork >= ForkName::Deneb {
+        body.push((
+            "blob_kzg_commitments",
+            list(PUBKEY, P::MaxBlobCommitmentsPerBlock::to_usize()),
+        ));
+    }
+    if fork >= ForkName::Electra {
+        body.push((
+            "execution_requests",
+            container(vec![
+                (
+                    "deposits",
+                    list(
+                        container(vec![
+                            ("pubkey", PUBKEY),
+                            ("withdrawal_credentials", ROOT),
+                            ("amount", U64),
+                            ("signature", SIGNATURE),
+                            ("index", U64),
+                        ]),
+                        P::MaxDepositRequestsPerPayload::to_usize(),
+                    ),
+                ),
+                (
+                    "withdrawals",
+                    list(
+                        container(vec![
+                            ("source_address", ADDRESS),
+                            ("validator_pubkey", PUBKEY),
+                            ("amount", U64),
+                        ]),
+                        P::MaxWithdrawalRequestsPerPayload::to_usize(),
+                    ),
+                ),
+                (
+                    "consolidations",
+                    list(
+                        container(vec![
+                            ("source_address", ADDRESS),
+                            ("source_pubkey", PUBKEY),
+                            ("target_pubkey", PUBKEY),
+                        ]),
+                        P::MaxConsolidationRequestsPerPayload::to_usize(),
+                    ),
+                ),
+            ]),
+        ));
+    }
+
+    container(vec![
+        (
+            "message",
+            container(vec![
+                ("slot", U64),
+                ("proposer_index", U64),
+                ("parent_root", ROOT),
+                ("state_root", ROOT),
+                ("body", container(body)),
+            ]),
+        ),
+        ("signature", SIGNATURE),
+    ])
+}
+
+pub(crate) fn beacon_state<P: Preset>(fork: ForkName) -> Schema {
+    let mut fields = vec![
+        ("genesis_time", U64),
+        ("genesis_validators_root", ROOT),
+        ("slot", U64),
+        (
+            "fork",
+            container(vec![
+                ("previous_version", Schema::Fixed(4)),
+                ("current_version", Schema::Fixed(4)),
+                ("epoch", U64),
+            ]),
+        ),
+        ("latest_block_header", block_header()),
+        (
+            "block_roots",
+            vector(ROOT, P::SlotsPerHistoricalRoot::to_usize()),
+        ),
+        (
+            "state_roots",
+            vector(ROOT, P::SlotsPerHistoricalRoot::to_usize()),
+        ),
+        ("historical_roots", list(ROOT, HISTORICAL_ROOTS_LIMIT)),
+        ("eth1_data", eth1_data()),
+        (
+            "eth1_data_votes",
+            list(eth1_data(), P::SlotsPerEth1VotingPeriod::to_usize()),
+        ),
+        ("eth1_deposit_index", U64),
+        (
+            "validators",
+            list(
+                container(vec![
+                    ("pubkey", PUBKEY),
+                    ("withdrawal_credentials", ROOT),
+                    ("effective_balance", U64),
+                    ("slashed", U8),
+                    ("activation_eligibility_epoch", U64),
+                    ("activation_epoch", U64),
+                    ("exit_epoch", U64),
+                    ("withdrawable_epoch", U64),
+                ]),
+                VALIDATOR_REGISTRY_LIMIT,
+            ),
+        ),
+        ("balances", list(U64, VALIDATOR_REGISTRY_LIMIT)),
+        (
+            "randao_mixes",
+            vector(ROOT, P::EpochsPerHistoricalVector::to_usize()),
+        ),
+        (
+            "slashings",
+            vector(U64, P::EpochsPerSlashingsVector::to_usize()),
+        ),
+    ];
+    if fork == ForkName::Phase0 {
+        let pending_attestation = container(vec![
+            (
+                "aggregation_bits",
+                Schema::Bits(MAX_VALIDATORS_PER_COMMITTEE),
+            ),
+            ("data", attestation_data()),
+            ("inclusion_delay", U64),
+            ("proposer_index", U64),
+        ]);
+        fields.push((
+            "previous_epoch_attestations",
+            list(
+                pending_attestation.clone(),
+                P::MaxPendingAttestations::to_usize(),
+            ),
+        ));
+        fields.push((
+            "current_epoch_attestations",
+            list(pending_attestation, P::MaxPendingAttestations::to_usize()),
+        ));
+    } else {
+        fields.push((
+            "previous_epoch_participation",
+            list(U8, VALIDATOR_REGISTRY_LIMIT),
+        ));
+        fields.push((
+            "current_epoch_participation",
+            list(U8, VALIDATOR_REGISTRY_LIMIT),
+        ));
+    }
+    fields.extend([
+        ("justification_bits", U8),
+        ("previous_justified_checkpoint", checkpoint()),
+        ("current_justified_checkpoint", checkpoint()),
+        ("finalized_checkpoint", checkpoint()),
+    ]);
+    if fork >= ForkName::Altair {
+        fields.push(("inactivity_scores", list(U64, VALIDATOR_REGISTRY_LIMIT)));
+        fields.push(("current_sync_committee", sync_committee::<P>()));
+        fields.push(("next_sync_committee", sync_committee::<P>()));
+    }
+    if fork >= ForkName::Bellatrix {
+        fields.push((
+            "latest_execution_payload_header",
+            execution_payload::<P>(fork, true),
+        ));
+    }
+    if fork >= ForkName::Capella {
+        fields.push(("next_withdrawal_index", U64));
+        fields.push(("next_withdrawal_validator_index", U64));
+        fields.push((
+            "historical_summaries",
+            list(
+                container(vec![
+                    ("block_summary_root", ROOT),
+                    ("state_summary_root", ROOT),
+                ]),
+                HISTORICAL_ROOTS_LIMIT,
+            ),
+        ));
+    }
+    if fork >= ForkName::Electra {
+        fields.extend([
+            ("deposit_requests_start_index", U64),
+            ("deposit_balance_to_consume", U64),
+            ("exit_balance_to_consume", U64),
+            ("earliest_exit_epoch", U64),
+            ("consolidation_balance_to_consume", U64),
+            ("earliest_consolidation_epoch", U64),
+            (
+                "pending_deposits",
+                list(
+                    container(vec![
+                        ("pubkey", PUBKEY),
+                        ("withdrawal_credentials", ROOT),
+                        ("amount", U64),
+                        ("signature", SIGNATURE),
+                        ("slot", U64),
+                    ]),
+                    PENDING_DEPOSITS_LIMIT,
+                ),
+            ),
+            (
+                "pending_partial_withdrawals",
+                list(
+                    container(vec![
+                        ("validator_index", U64),
+                        ("amount", U64),
+                        ("withdrawable_epoch", U64),
+                    ]),
+                    P::PendingPartialWithdrawalsLimit::to_usize(),
+                ),
+            ),
+            (
+                "pending_consolidations",
+                list(
+                    container(vec![("source_index", U64), ("target_index", U64)]),
+                    P::PendingConsolidationsLimit::to_usize(),
+                ),
+            ),
+        ]);
+    }
+    container(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use sszb::SszEncode;
+    use tree_hash::TreeHash;
+
+    use super::*;
+    use crate::{BeaconStateAnyFork, MainnetPreset, MinimalPreset, SignedBeaconBlockAnyFork};
+
+    /// Fixed-size parts in order, then the variable-size ones behind offsets.
+    fn encode_parts(parts: Vec<(bool, Vec<u8>)>) -> Vec<u8> {
+        let fixed_len: usize = parts
+            .iter()
+            .map(|(variable, part)| if *variable { OFFSET } else { part.len() })
+            .sum();
+        let mut out = Vec::new();
+        let mut tail = Vec::new();
+        for (variable, part) in parts {
+            if variable {
+                out.extend_from_slice(&((fixed_len + tail.len()) as u32).to_le_bytes());
+                tail.extend(part);
+            } else {
+                out.extend(part);
+            }
+        }
+        out.extend(tail);
+        out
+    }
+
+    /// An encoding laid out as `schema` says, with zeroed fixed-size fields
+    /// and one element in every list, so each list's element layout counts
+    /// too. `[0x01]` is both a one-byte byte list and an empty bitlist.
+    fn example(schema: &Schema) -> Vec<u8> {
+        let part = |schema: &Schema| (schema.fixed_len().is_none(), example(schema));
+        match schema {
+            Schema::Fixed(len) => vec![0; *len],
+            Schema::Bytes(_) | Schema::Bits(_) => vec![1],
+            Schema::Container(fields) => {
+                encode_parts(fields.iter().map(|(_, field)| part(field)).collect())
+            }
+            Schema::Vector(elem, len) => encode_parts((0..*len).map(|_| part(elem)).collect()),
+            Schema::List(elem, _) => encode_parts(vec![part(elem)]),
+        }
+    }
+
+    /// The real types must decode the schema's layout and re-encode it
+    /// byte for byte. A wrong length or a missing or extra variable-size
+    /// field anywhere shifts the offsets, so decoding fails or the lengths
+    /// differ.
+    ///
+    /// Their roots must match the schema's too, which pins every list limit.
+    fn check_against_types<P: Preset>() {
+        for fork in ForkName::ALL {
+            let schema = signed_beacon_block::<P>(fork);
+            let bytes = example(&schema);
+            let block = SignedBeaconBlockAnyFork::<P>::from_ssz_bytes_for_fork(&bytes, fork)
+                .unwrap_or_else(|e| panic!("{} {fork} block: {e:?}", P::NAME));
+            assert_eq!(block.to_ssz(), bytes, "{} {fork} block", P::NAME);
+            assert_eq!(
+                schema.hash_tree_root(&bytes),
+                Some(block.tree_hash_root()),
+                "{} {fork} block root",
+                P::NAME
+            );
+
+            let schema = beacon_state::<P>(fork);
+            let bytes = example(&schema);
+            let state = BeaconStateAnyFork::<P>::from_ssz_bytes_for_fork(&bytes, fork)
+                .unwrap_or_else(|e| panic!("{} {fork} state: {e:?}", P::NAME));
+            assert_eq!(state.to_ssz(), bytes, "{} {fork} state", P::NAME);
+            assert_eq!(
+                schema.hash_tree_root(&bytes),
+                Some(state.tree_hash_root()),
+                "{} {fork} state root",
+                P::NAME
+            );
+        }
+    }
+
+    #[test]
+    fn mainnet_schemas_match_types() {
+        check_against_types::<MainnetPreset>();
+    }
+
+    #[test]
+    fn minimal_schemas_match_types() {
+        check_against_types::<MinimalPreset>();
+    }
+}
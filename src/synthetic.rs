@@ -0,0 +1,885 @@
+use std::fmt;
+
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+use ghilhouse::{List, Vector};
+use ssz_types::{BitList, BitVector, FixedVector, VariableList};
+use sszb::{PKBytes, Sig, SszDecode};
+use typenum::Unsigned;
+
+use crate::beacon_block;
+use crate::beacon_state::{BeaconBlockHeader, Checkpoint, Eth1Data};
+use crate::{
+    Attestation, AttestationData, AttesterSlashing, BeaconBlock, BeaconBlockBody, BeaconState,
+    BeaconStateAnyFork, BeaconStateElectra, BlsToExecutionChange, Deposit, DepositData,
+    ExecutionPayload, ExecutionPayloadHeader, Fork, ForkName, HistoricalSummary,
+    IndexedAttestation, Network, Preset, ProposerSlashing, SignedBeaconBlock,
+    SignedBeaconBlockHeader, SignedBlsToExecutionChange, SignedVoluntaryExit, SyncAggregate,
+    SyncCommittee, TxOpaque, TxOpaqueError, Validator, VoluntaryExit, Withdrawal,
+};
+
+const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+const MAX_EFFECTIVE_BALANCE: u64 = 32_000_000_000;
+const TIMELY_SOURCE_TARGET_HEAD: u8 = 0b111;
+/// `MAX_VALIDATORS_PER_COMMITTEE`, the bound of aggregation bits and attesting indices.
+const MAX_VALIDATORS_PER_COMMITTEE: usize = 2048;
+/// The compressed point at infinity. Random bytes are almost never a valid
+/// G2 point, and lighthouse and grandine reject those on decode.
+const INFINITY_SIGNATURE: [u8; 96] = {
+    let mut signature = [0; 96];
+    signature[0] = 0xc0;
+    signature
+};
+const PUBKEY_LEN: usize = 48;
+/// `UNSET_DEPOSIT_REQUESTS_START_INDEX`, until the first deposit request lands.
+const UNSET_DEPOSIT_REQUESTS_START_INDEX: u64 = u64::MAX;
+/// `MAX_SEED_LOOKAHEAD`, which `compute_activation_exit_epoch` adds.
+const MAX_SEED_LOOKAHEAD: u64 = 4;
+
+/// SplitMix64, so a seed always yields the same object on every platform
+/// without pulling in an rng crate.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, `n` must be non-zero.
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub(crate) fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub(crate) fn fill(&mut self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    pub(crate) fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = [0; N];
+        self.fill(&mut bytes);
+        bytes
+    }
+
+    pub(crate) fn root(&mut self) -> B256 {
+        B256::from(self.bytes::<32>())
+    }
+
+    /// Some SSZ type from `len` random bytes, for leaf types such as public
+    /// keys that are only ever built by decoding.
+    pub(crate) fn decode<T: SszDecode>(&mut self, len: usize) -> T {
+        let mut bytes = vec![0; len];
+        self.fill(&mut bytes);
+        T::from_ssz_bytes(&bytes).expect("any bytes of the right length decode")
+    }
+}
+
+/// How many of the three timely flags each validator has in the participation lists.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Participation {
+    /// Every validator timely on source, target and head.
+    Full,
+    /// No flags set, as during an inactivity leak.
+    Empty,
+    /// Each flag set independently with this probability.
+    Random(f64),
+}
+
+impl std::str::FromStr for Participation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Self::Full),
+            "none" => Ok(Self::Empty),
+            rate => match rate.parse::<f64>() {
+                Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(Self::Random(rate)),
+                _ => Err(format!(
+                    "participation must be full, none or a rate in 0..=1, got {s:?}"
+                )),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    /// A list was asked to hold more than its SSZ limit.
+    TooMany {
+        field: &'static str,
+        count: usize,
+        limit: usize,
+    },
+    /// The sync committees are drawn from the validators, so there must be some.
+    NoValidators,
+    /// The network is in a fork the generator has no layout for at the slot.
+    UnsupportedFork(ForkName),
+    Transactions(TxOpaqueError),
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooMany {
+                field,
+                count,
+                limit,
+            } => write!(f, "{count} {field} exceed the limit of {limit}"),
+            Self::NoValidators => write!(f, "a state needs at least one validator"),
+            Self::UnsupportedFork(fork) => write!(f, "cannot generate a {fork} state"),
+            Self::Transactions(e) => write!(f, "transactions: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+fn check_limit(field: &'static str, count: usize, limit: usize) -> Result<(), GenerateError> {
+    if count > limit {
+        return Err(GenerateError::TooMany {
+            field,
+            count,
+            limit,
+        });
+    }
+    Ok(())
+}
+
+/// Deterministic Deneb [`BeaconState`] of any size, so state benchmarks
+/// don't depend on a mainnet download and can be run across validator counts.
+#[derive(Clone, Debug)]
+pub struct StateGenerator {
+    seed: u64,
+    slot: Option<u64>,
+    validators: usize,
+    eth1_votes: usize,
+    historical_summaries: usize,
+    participation: Participation,
+}
+
+impl StateGenerator {
+    /// Defaults to roughly mainnet's shape at Deneb: a million validators,
+    /// a half-full eth1 voting period and high participation.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            slot: None,
+            validators: 1_000_000,
+            eth1_votes: 1024,
+            historical_summaries: 256,
+            participation: Participation::Random(0.95),
+        }
+    }
+
+    /// Defaults to the first slot of Deneb on the network passed to [`StateGenerator::generate`].
+    pub fn with_slot(mut self, slot: u64) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    pub fn with_validators(mut self, validators: usize) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    pub fn with_eth1_votes(mut self, eth1_votes: usize) -> Self {
+        self.eth1_votes = eth1_votes;
+        self
+    }
+
+    pub fn with_historical_summaries(mut self, historical_summaries: usize) -> Self {
+        self.historical_summaries = historical_summaries;
+        self
+    }
+
+    pub fn with_participation(mut self, participation: Participation) -> Self {
+        self.participation = participation;
+        self
+    }
+
+    pub fn generate<P: Preset>(&self, network: Network) -> Result<BeaconState<P>, GenerateError> {
+        if self.validators == 0 {
+            return Err(GenerateError::NoValidators);
+        }
+        check_limit(
+            "eth1 votes",
+            self.eth1_votes,
+            P::SlotsPerEth1VotingPeriod::to_usize(),
+        )?;
+        check_limit("historical summaries", self.historical_summaries, 1 << 24)?;
+
+        let mut rng = Rng::new(self.seed);
+        let slot = self.slot.unwrap_or_else(|| {
+            network
+                .fork_epochs()
+                .deneb
+                .saturating_mul(network.slots_per_epoch())
+        });
+        let epoch = slot / network.slots_per_epoch();
+        let validator_count = self.validators as u64;
+
+        let validators: Vec<Validator> = (0..self.validators)
+            .map(|_| {
+                let activation_epoch = rng.below(epoch + 1);
+                // 0x01 credentials: prefix, 11 zero bytes, execution address
+                let mut withdrawal_credentials = [0; 32];
+                withdrawal_credentials[0] = 1;
+                rng.fill(&mut withdrawal_credentials[12..]);
+                Validator {
+                    pubkey: rng.decode::<PKBytes>(PUBKEY_LEN),
+                    withdrawal_credentials: B256::from(withdrawal_credentials),
+                    effective_balance: MAX_EFFECTIVE_BALANCE,
+                    slashed: false,
+                    activation_eligibility_epoch: activation_epoch.saturating_sub(1),
+                    activation_epoch,
+                    exit_epoch: FAR_FUTURE_EPOCH,
+                    withdrawable_epoch: FAR_FUTURE_EPOCH,
+                }
+            })
+            .collect();
+        // up to 0.1 ETH of unswept rewards on top of the effective balance
+        let balances = (0..self.validators).map(|_| MAX_EFFECTIVE_BALANCE + rng.below(100_000_000));
+        let balances = List::try_from_iter(balances).expect("validator count is below the limit");
+
+        let participation = |rng: &mut Rng| -> Vec<u8> {
+            (0..self.validators)
+                .map(|_| match self.participation {
+                    Participation::Full => TIMELY_SOURCE_TARGET_HEAD,
+                    Participation::Empty => 0,
+                    Participation::Random(rate) => (0..3)
+                        .filter(|_| rng.chance(rate))
+                        .fold(0, |flags, flag| flags | (1 << flag)),
+                })
+                .collect()
+        };
+        let previous_epoch_participation = List::try_from_iter(participation(&mut rng))
+            .expect("validator count is below the limit");
+        let current_epoch_participation = List::try_from_iter(participation(&mut rng))
+            .expect("validator count is below the limit");
+
+        let sync_committee = |rng: &mut Rng| SyncCommittee::<P> {
+            pubkeys: FixedVector::new(
+                (0..P::SyncCommitteeSize::to_usize())
+                    .map(|_| {
+                        validators[rng.below(validator_count) as usize]
+                            .pubkey
+                            .clone()
+                    })
+                    .collect(),
+            )
+            .expect("exactly SYNC_COMMITTEE_SIZE keys"),
+            aggregate_pubkey: rng.decode::<PKBytes>(PUBKEY_LEN),
+        };
+        let current_sync_committee = sync_committee(&mut rng);
+        let next_sync_committee = sync_committee(&mut rng);
+
+        let roots = |rng: &mut Rng, n: usize| (0..n).map(|_| rng.root()).collect::<Vec<_>>();
+        let block_roots =
+            Vector::try_from_iter(roots(&mut rng, P::SlotsPerHistoricalRoot::to_usize()))
+                .expect("exactly SLOTS_PER_HISTORICAL_ROOT roots");
+        let state_roots =
+            Vector::try_from_iter(roots(&mut rng, P::SlotsPerHistoricalRoot::to_usize()))
+                .expect("exactly SLOTS_PER_HISTORICAL_ROOT roots");
+        let randao_mixes =
+            Vector::try_from_iter(roots(&mut rng, P::EpochsPerHistoricalVector::to_usize()))
+                .expect("exactly EPOCHS_PER_HISTORICAL_VECTOR mixes");
+        let slashings = Vector::try_from_iter(
+            std::iter::repeat(0).take(P::EpochsPerSlashingsVector::to_usize()),
+        )
+        .expect("exactly EPOCHS_PER_SLASHINGS_VECTOR entries");
+
+        let eth1_data = |rng: &mut Rng| Eth1Data {
+            deposit_root: rng.root(),
+            deposit_count: validator_count,
+            block_hash: rng.root(),
+        };
+        let eth1_data_votes = (0..self.eth1_votes)
+            .map(|_| eth1_data(&mut rng))
+            .collect::<Vec<_>>();
+        let historical_summaries = (0..self.historical_summaries)
+            .map(|_| HistoricalSummary {
+                block_summary_root: rng.root(),
+                state_summary_root: rng.root(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut justification_bits = BitVector::new();
+        for bit in 0..4 {
+            justification_bits
+                .set(bit, true)
+                .expect("justification bits has four bits");
+        }
+        let checkpoint = |rng: &mut Rng, epoch: u64| Checkpoint {
+            epoch,
+            root: rng.root(),
+        };
+
+        Ok(BeaconState {
+            genesis_time: network.genesis_time(),
+            genesis_validators_root: rng.root(),
+            slot,
+            fork: Fork {
+                previous_version: network.fork_versions().capella,
+                current_version: network.fork_versions().deneb,
+                epoch: network.fork_epochs().deneb.min(epoch),
+            },
+            latest_block_header: BeaconBlockHeader {
+                slot,
+                proposer_index: rng.below(validator_count),
+                parent_root: rng.root(),
+                state_root: B256::ZERO,
+                body_root: rng.root(),
+            },
+            block_roots,
+            state_roots,
+            historical_roots: List::default(),
+            eth1_data: eth1_data(&mut rng),
+            eth1_data_votes: List::try_from_iter(eth1_data_votes)
+                .expect("eth1 votes checked against the limit"),
+            eth1_deposit_index: validator_count,
+            validators: List::try_from_iter(validators)
+                .expect("validator count is below the limit"),
+            balances,
+            randao_mixes,
+            slashings,
+            previous_epoch_participation,
+            current_epoch_participation,
+            justification_bits,
+            previous_justified_checkpoint: checkpoint(&mut rng, epoch.saturating_sub(2)),
+            current_justified_checkpoint: checkpoint(&mut rng, epoch.saturating_sub(1)),
+            finalized_checkpoint: checkpoint(&mut rng, epoch.saturating_sub(2)),
+            inactivity_scores: List::try_from_iter(std::iter::repeat(0).take(self.validators))
+                .expect("validator count is below the limit"),
+            current_sync_committee,
+            next_sync_committee,
+            latest_execution_payload_header: ExecutionPayloadHeader {
+                parent_hash: rng.root(),
+                state_root: rng.root(),
+                receipts_root: rng.root(),
+                prev_randao: rng.root(),
+                block_number: rng.below(1 << 24),
+                gas_limit: 30_000_000,
+                gas_used: rng.below(30_000_000),
+                block_hash: rng.root(),
+                transactions_root: rng.root(),
+                withdrawals_root: rng.root(),
+                ..Default::default()
+            },
+            next_withdrawal_index: rng.below(1 << 32),
+            next_withdrawal_validator_index: rng.below(validator_count),
+            historical_summaries: List::try_from_iter(historical_summaries)
+                .expect("historical summaries checked against the limit"),
+        })
+    }
+
+    /// Like [`StateGenerator::generate`], but in whichever fork `network`
+    /// is in at the slot: Deneb, or Electra upgraded from it the way
+    /// `upgrade_to_electra` does. Local devnets on the minimal preset start
+    /// in Electra, so this is the only way to get a state they accept.
+    pub fn generate_any_fork<P: Preset>(
+        &self,
+        network: Network,
+    ) -> Result<BeaconStateAnyFork<P>, GenerateError> {
+        let state = self.generate::<P>(network)?;
+        match network.fork_at_slot(state.slot) {
+            ForkName::Deneb => Ok(BeaconStateAnyFork::Deneb(state)),
+            ForkName::Electra => Ok(BeaconStateAnyFork::Electra(upgrade_to_electra(
+                state, network,
+            ))),
+            fork => Err(GenerateError::UnsupportedFork(fork)),
+        }
+    }
+}
+
+/// No validator is exiting, no deposits are pending and the churn balances
+/// start empty, so only the exit and consolidation epochs need working out.
+fn upgrade_to_electra<P: Preset>(state: BeaconState<P>, network: Network) -> BeaconStateElectra<P> {
+    let epoch = state.slot / network.slots_per_epoch();
+    let activation_exit_epoch = epoch + 1 + MAX_SEED_LOOKAHEAD;
+    BeaconStateElectra {
+        genesis_time: state.genesis_time,
+        genesis_validators_root: state.genesis_validators_root,
+        slot: state.slot,
+        fork: Fork {
+            previous_version: network.fork_versions().deneb,
+            current_version: network.fork_versions().electra,
+            epoch: network.fork_epochs().electra.min(epoch),
+        },
+        latest_block_header: state.latest_block_header,
+        block_roots: state.block_roots,
+        state_roots: state.state_roots,
+        historical_roots: state.historical_roots,
+        eth1_data: state.eth1_data,
+        eth1_data_votes: state.eth1_data_votes,
+        eth1_deposit_index: state.eth1_deposit_index,
+        validators: state.validators,
+        balances: state.balances,
+        randao_mixes: state.randao_mixes,
+        slashings: state.slashings,
+        previous_epoch_participation: state.previous_epoch_participation,
+        current_epoch_participation: state.current_epoch_participation,
+        justification_bits: state.justification_bits,
+        previous_justified_checkpoint: state.previous_justified_checkpoint,
+        current_justified_checkpoint: state.current_justified_checkpoint,
+        finalized_checkpoint: state.finalized_checkpoint,
+        inactivity_scores: state.inactivity_scores,
+        current_sync_committee: state.current_sync_committee,
+        next_sync_committee: state.next_sync_committee,
+        latest_execution_payload_header: state.latest_execution_payload_header,
+        next_withdrawal_index: state.next_withdrawal_index,
+        next_withdrawal_validator_index: state.next_withdrawal_validator_index,
+        historical_summaries: state.historical_summaries,
+        deposit_requests_start_index: UNSET_DEPOSIT_REQUESTS_START_INDEX,
+        deposit_balance_to_consume: 0,
+        exit_balance_to_consume: 0,
+        earliest_exit_epoch: activation_exit_epoch + 1,
+        consolidation_balance_to_consume: 0,
+        earliest_consolidation_epoch: activation_exit_epoch,
+        pending_deposits: List::default(),
+        pending_partial_withdrawals: List::default(),
+        pending_consolidations: List::default(),
+    }
+}
+
+/// Deterministic Deneb [`SignedBeaconBlock`], either shaped like a typical
+/// mainnet block or, with [`BlockGenerator::worst_case`], with every operation
+/// list filled to its bound.
+#[derive(Clone, Debug)]
+pub struct BlockGenerator {
+    seed: u64,
+    slot: Option<u64>,
+    proposer_slashings: usize,
+    attester_slashings: usize,
+    attestations: usize,
+    attestation_bits: usize,
+    deposits: usize,
+    voluntary_exits: usize,
+    bls_to_execution_changes: usize,
+    /// `None` fills the list to the preset's limit.
+    withdrawals: Option<usize>,
+    blob_commitments: Option<usize>,
+    transactions: usize,
+    transaction_size: usize,
+}
+
+impl BlockGenerator {
+    /// Defaults to roughly a mainnet Deneb block: a hundred attestations of
+    /// ~400 validators each, full withdrawals, three blobs and 150 transactions.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            slot: None,
+            proposer_slashings: 0,
+            attester_slashings: 0,
+            attestations: 100,
+            attestation_bits: 400,
+            deposits: 0,
+            voluntary_exits: 0,
+            bls_to_execution_changes: 0,
+            withdrawals: None,
+            blob_commitments: Some(3),
+            transactions: 150,
+            transaction_size: 600,
+        }
+    }
+
+    /// Every operation list at its bound: 16 proposer slashings, 2 attester
+    /// slashings of 2048 indices each, 128 attestations with 2048 aggregation
+    /// bits, 16 deposits, exits and BLS changes, and the preset's limit of
+    /// withdrawals and blob commitments. Transactions keep their default,
+    /// the spec's limits on them are far beyond any block.
+    pub fn worst_case(seed: u64) -> Self {
+        Self {
+            proposer_slashings: 16,
+            attester_slashings: 2,
+            attestations: 128,
+            attestation_bits: MAX_VALIDATORS_PER_COMMITTEE,
+            deposits: 16,
+            voluntary_exits: 16,
+            bls_to_execution_changes: 16,
+            withdrawals: None,
+            blob_commitments: None,
+            ..Self::new(seed)
+        }
+    }
+
+    /// Defaults to the first slot of Deneb on the network passed to [`BlockGenerator::generate`].
+    pub fn with_slot(mut self, slot: u64) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    pub fn with_attestations(mut self, attestations: usize, bits: usize) -> Self {
+        self.attestations = attestations;
+        self.attestation_bits = bits;
+        self
+    }
+
+    pub fn with_deposits(mut self, deposits: usize) -> Self {
+        self.deposits = deposits;
+        self
+    }
+
+    pub fn with_blob_commitments(mut self, blob_commitments: usize) -> Self {
+        self.blob_commitments = Some(blob_commitments);
+        self
+    }
+
+    /// `count` transactions of `size` random bytes each.
+    pub fn with_transactions(mut self, count: usize, size: usize) -> Self {
+        self.transactions = count;
+        self.transaction_size = size;
+        self
+    }
+
+    pub fn generate<P: Preset>(
+        &self,
+        network: Network,
+    ) -> Result<SignedBeaconBlock<P>, GenerateError> {
+        let max_withdrawals = P::MaxWithdrawalsPerPayload::to_usize();
+        let max_blob_commitments = P::MaxBlobCommitmentsPerBlock::to_usize();
+        let withdrawals = self.withdrawals.unwrap_or(max_withdrawals);
+        let blob_commitments = self.blob_commitments.unwrap_or(max_blob_commitments);
+        check_limit("proposer slashings", self.proposer_slashings, 16)?;
+        check_limit("attester slashings", self.attester_slashings, 2)?;
+        check_limit("attestations", self.attestations, 128)?;
+        check_limit(
+            "aggregation bits",
+            self.attestation_bits,
+            MAX_VALIDATORS_PER_COMMITTEE,
+        )?;
+        check_limit("deposits", self.deposits, 16)?;
+        check_limit("voluntary exits", self.voluntary_exits, 16)?;
+        check_limit(
+            "bls to execution changes",
+            self.bls_to_execution_changes,
+            16,
+        )?;
+        check_limit("withdrawals", withdrawals, max_withdrawals)?;
+        check_limit("blob commitments", blob_commitments, max_blob_commitments)?;
+
+        let mut rng = Rng::new(self.seed);
+        let slot = self.slot.unwrap_or_else(|| {
+            network
+                .fork_epochs()
+                .deneb
+                .saturating_mul(network.slots_per_epoch())
+        });
+        let epoch = slot / network.slots_per_epoch();
+        let validator_index = |rng: &mut Rng| rng.below(1 << 20);
+        let signature =
+            Sig::from_ssz_bytes(&INFINITY_SIGNATURE).expect("the infinity signature decodes");
+        let checkpoint = |rng: &mut Rng, epoch: u64| beacon_block::Checkpoint {
+            epoch,
+            root: rng.root(),
+        };
+        let attestation_data = |rng: &mut Rng| AttestationData {
+            slot: slot.saturating_sub(1 + rng.below(32)),
+            index: rng.below(64),
+            beacon_block_root: rng.root(),
+            source: checkpoint(rng, epoch.saturating_sub(2)),
+            target: checkpoint(rng, epoch.saturating_sub(1)),
+        };
+
+        let proposer_slashings = (0..self.proposer_slashings)
+            .map(|_| {
+                let proposer_index = validator_index(&mut rng);
+                let signed_header = |rng: &mut Rng| SignedBeaconBlockHeader {
+                    message: beacon_block::BeaconBlockHeader {
+                        slot: slot.saturating_sub(1),
+                        proposer_index,
+                        parent_root: rng.root(),
+                        state_root: rng.root(),
+                        body_root: rng.root(),
+                    },
+                    signature: signature.clone(),
+                };
+                ProposerSlashing {
+                    signed_header_1: signed_header(&mut rng),
+                    signed_header_2: signed_header(&mut rng),
+                }
+            })
+            .collect();
+
+        // double votes by a whole committee, the largest an attester slashing gets
+        let attester_slashings = (0..self.attester_slashings)
+            .map(|_| {
+                let first = validator_index(&mut rng);
+                let data = attestation_data(&mut rng);
+                let indexed = |rng: &mut Rng| IndexedAttestation {
+                    attesting_indices: VariableList::new(
+                        (first..first + self.attestation_bits as u64).collect(),
+                    )
+                    .expect("aggregation bits checked against the limit"),
+                    data: AttestationData {
+                        beacon_block_root: rng.root(),
+                        ..data.clone()
+                    },
+                    signature: signature.clone(),
+                };
+                AttesterSlashing {
+                    attestation_1: indexed(&mut rng),
+                    attestation_2: indexed(&mut rng),
+                }
+            })
+            .collect();
+
+        let attestations = (0..self.attestations)
+            .map(|_| {
+                let mut aggregation_bits = BitList::with_capacity(self.attestation_bits)
+                    .expect("aggregation bits checked against the limit");
+                for bit in 0..self.attestation_bits {
+                    aggregation_bits
+                        .set(bit, true)
+                        .expect("bit is below the length");
+                }
+                Attestation {
+                    aggregation_bits,
+                    data: attestation_data(&mut rng),
+                    signature: signature.clone(),
+                }
+            })
+            .collect();
+
+        let deposits = (0..self.deposits)
+            .map(|_| Deposit {
+                // DEPOSIT_CONTRACT_TREE_DEPTH + 1, the extra node mixes in the deposit count
+                proof: FixedVector::new((0..33).map(|_| rng.root()).collect())
+                    .expect("exactly 33 proof nodes"),
+                data: DepositData {
+                    pubkey: rng.decode::<PKBytes>(PUBKEY_LEN),
+                    withdrawal_credentials: rng.root(),
+                    amount: MAX_EFFECTIVE_BALANCE,
+                    signature: signature.clone(),
+                },
+            })
+            .collect();
+
+        let voluntary_exits = (0..self.voluntary_exits)
+            .map(|_| SignedVoluntaryExit {
+                message: VoluntaryExit {
+                    epoch,
+                    validator_index: validator_index(&mut rng),
+                },
+                signature: signature.clone(),
+            })
+            .collect();
+
+        let bls_to_execution_changes = (0..self.bls_to_execution_changes)
+            .map(|_| SignedBlsToExecutionChange {
+                message: BlsToExecutionChange {
+                    validator_index: validator_index(&mut rng),
+                    from_bls_pubkey: rng.decode::<PKBytes>(PUBKEY_LEN),
+                    to_execution_address: Address::from(rng.bytes::<20>()),
+                },
+                signature: signature.clone(),
+            })
+            .collect();
+
+        let mut sync_committee_bits = BitVector::new();
+        for bit in 0..P::SyncCommitteeSize::to_usize() {
+            sync_committee_bits
+                .set(bit, true)
+                .expect("bit is below SYNC_COMMITTEE_SIZE");
+        }
+
+        let mut transactions = TxOpaque::default();
+        let mut tx = vec![0; self.transaction_size];
+        for _ in 0..self.transactions {
+            rng.fill(&mut tx);
+            transactions
+                .push(&tx)
+                .map_err(GenerateError::Transactions)?;
+        }
+        let first_withdrawal = rng.below(1 << 32);
+        let withdrawals = (0..withdrawals as u64)
+            .map(|i| Withdrawal {
+                index: first_withdrawal + i,
+                validator_index: validator_index(&mut rng),
+                address: Address::from(rng.bytes::<20>()),
+                amount: rng.below(100_000_000),
+            })
+            .collect();
+        let mut logs_bloom = vec![0; 256];
+        rng.fill(&mut logs_bloom);
+
+        let execution_payload = ExecutionPayload {
+            parent_hash: rng.root(),
+            fee_recipient: Address::from(rng.bytes::<20>()),
+            state_root: rng.root(),
+            receipts_root: rng.root(),
+            logs_bloom: FixedVector::new(logs_bloom).expect("exactly 256 bytes"),
+            prev_randao: rng.root(),
+            block_number: rng.below(1 << 24),
+            gas_limit: 30_000_000,
+            gas_used: rng.below(30_000_000),
+            timestamp: slot.saturating_mul(12).saturating_add(1_606_824_023),
+            extra_data: VariableList::new(rng.bytes::<32>().to_vec()).expect("exactly 32 bytes"),
+            base_fee_per_gas: U256::from(rng.below(100_000_000_000)),
+            block_hash: rng.root(),
+            transactions,
+            withdrawals: VariableList::new(withdrawals)
+                .expect("withdrawals checked against the limit"),
+            // gas only pays for six blobs, however many commitments the list holds
+            blob_gas_used: blob_commitments.min(6) as u64 * 131_072,
+            excess_blob_gas: 0,
+        };
+
+        let body = BeaconBlockBody {
+            randao_reveal: signature.clone(),
+            eth1_data: beacon_block::Eth1Data {
+                deposit_root: rng.root(),
+                deposit_count: rng.below(1 << 24),
+                block_hash: rng.root(),
+            },
+            graffiti: FixedBytes::from(rng.bytes::<32>()),
+            proposer_slashings: VariableList::new(proposer_slashings)
+                .expect("proposer slashings checked against the limit"),
+            attester_slashings: VariableList::new(attester_slashings)
+                .expect("attester slashings checked against the limit"),
+            attestations: VariableList::new(attestations)
+                .expect("attestations checked against the limit"),
+            deposits: VariableList::new(deposits).expect("deposits checked against the limit"),
+            voluntary_exits: VariableList::new(voluntary_exits)
+                .expect("voluntary exits checked against the limit"),
+            sync_aggregate: SyncAggregate {
+                sync_committee_bits,
+                sync_committee_signature: signature.clone(),
+            },
+            execution_payload,
+            bls_to_execution_changes: VariableList::new(bls_to_execution_changes)
+                .expect("bls to execution changes checked against the limit"),
+            blob_kzg_commitments: VariableList::new(
+                (0..blob_commitments).map(|_| rng.bytes::<48>()).collect(),
+            )
+            .expect("blob commitments checked against the limit"),
+        };
+
+        Ok(SignedBeaconBlock {
+            message: BeaconBlock {
+                slot,
+                proposer_index: validator_index(&mut rng),
+                parent_root: rng.root(),
+                state_root: rng.root(),
+                body,
+            },
+            signature: signature.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sszb::SszEncode;
+
+    use super::*;
+    use crate::{
+        verify_block, verify_state, ArenaSpec, MainnetPreset, MainnetSpec, MinimalPreset,
+        MinimalSpec,
+    };
+
+    /// Checks the generated block against every library the enabled features
+    /// bring in, e.g. `cargo test --features sigp,grandine`.
+    fn round_trip(generator: BlockGenerator) {
+        let block = generator
+            .generate::<<MainnetSpec as ArenaSpec>::Preset>(Network::Mainnet)
+            .unwrap();
+        if let Err(e) = verify_block::<MainnetSpec>(&block.to_ssz()) {
+            panic!("{e}");
+        }
+    }
+
+    #[test]
+    fn typical_block_round_trips() {
+        round_trip(BlockGenerator::new(0));
+    }
+
+    #[test]
+    fn worst_case_block_round_trips() {
+        round_trip(BlockGenerator::worst_case(0));
+    }
+
+    /// A few validators keep the states small; the preset-sized vectors and
+    /// sync committees are there regardless.
+    fn small_state() -> StateGenerator {
+        StateGenerator::new(0)
+            .with_validators(64)
+            .with_eth1_votes(16)
+            .with_historical_summaries(4)
+    }
+
+    #[test]
+    fn mainnet_state_verifies() {
+        let state = small_state()
+            .generate_any_fork::<MainnetPreset>(Network::Mainnet)
+            .unwrap();
+        assert_eq!(state.fork_name(), ForkName::Deneb);
+        if let Err(e) = verify_state::<MainnetSpec>(&state.to_ssz()) {
+            panic!("{e}");
+        }
+    }
+
+    #[test]
+    fn minimal_state_verifies() {
+        let state = small_state()
+            .generate_any_fork::<MinimalPreset>(Network::Minimal)
+            .unwrap();
+        // minimal devnets start in electra
+        assert_eq!(state.fork_name(), ForkName::Electra);
+        if let Err(e) = verify_state::<MinimalSpec>(&state.to_ssz()) {
+            panic!("{e}");
+        }
+    }
+
+    #[test]
+    fn fork_versions_and_genesis_time_follow_the_network() {
+        for network in [Network::Holesky, Network::Sepolia] {
+            let state = small_state().generate::<MainnetPreset>(network).unwrap();
+            let versions = network.fork_versions();
+            assert_eq!(state.genesis_time, network.genesis_time());
+            assert_eq!(state.fork.previous_version, versions.capella);
+            assert_eq!(state.fork.current_version, versions.deneb);
+        }
+    }
+
+    #[test]
+    fn no_validators() {
+        assert_eq!(
+            small_state()
+                .with_validators(0)
+                .generate::<MainnetPreset>(Network::Mainnet),
+            Err(GenerateError::NoValidators)
+        );
+    }
+
+    #[test]
+    fn eth1_votes_limit() {
+        let limit = <MinimalPreset as Preset>::SlotsPerEth1VotingPeriod::to_usize();
+        assert!(small_state()
+            .with_eth1_votes(limit)
+            .generate::<MinimalPreset>(Network::Minimal)
+            .is_ok());
+        assert_eq!(
+            small_state()
+                .with_eth1_votes(limit + 1)
+                .generate::<MinimalPreset>(Network::Minimal),
+            Err(GenerateError::TooMany {
+                field: "eth1 votes",
+                count: limit + 1,
+                limit,
+            })
+        );
+    }
+}
@@ -1,9 +1,64 @@
-use bytes::{Buf, BufMut};
-use sszb::{
-    read_offset_from_slice, sanitize_offset, DecodeError, SszDecode, SszEncode,
-    BYTES_PER_LENGTH_OFFSET,
+use std::fmt;
+
+use bytes::{Buf, BufMut, Bytes};
+use grandine_ssz::{ByteList, ContiguousList, ReadError, SszReadDefault};
+use sigp_types::{EthSpec, TransactionsOpaque};
+use ssz_types::VariableList;
+use sszb::{read_offset_from_slice, DecodeError, SszDecode, SszEncode, BYTES_PER_LENGTH_OFFSET};
+use tree_hash::{
+    merkle_root, mix_in_length, Hash256, MerkleHasher, PackedEncoding, TreeHash, TreeHashType,
+    BYTES_PER_CHUNK,
 };
 
+pub const MAX_BYTES_PER_TRANSACTION: usize = 1073741824;
+pub const MAX_TRANSACTIONS_PER_PAYLOAD: usize = 1048576;
+
+/// The spec's `ByteList[MAX_BYTES_PER_TRANSACTION]`.
+pub type Transaction = VariableList<u8, typenum::U1073741824>;
+pub type Transactions = VariableList<Transaction, typenum::U1048576>;
+
+/// A limit the spec puts on a payload's transaction list was exceeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxOpaqueError {
+    TooManyTransactions {
+        limit: usize,
+    },
+    TransactionTooLarge {
+        len: usize,
+        limit: usize,
+    },
+    /// The encoding would need offsets past `u32::MAX`.
+    OffsetOverflow {
+        len: usize,
+    },
+}
+
+impl fmt::Display for TxOpaqueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyTransactions { limit } => {
+                write!(
+                    f,
+                    "payload already holds the maximum of {limit} transactions"
+                )
+            }
+            Self::TransactionTooLarge { len, limit } => {
+                write!(
+                    f,
+                    "transaction of {len} bytes exceeds the maximum of {limit}"
+                )
+            }
+            Self::OffsetOverflow { len } => {
+                write!(f, "encoding of {len} bytes does not fit 4-byte offsets")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TxOpaqueError {}
+
+/// Every transaction of a payload in one flat buffer, with `offsets[i]`
+/// the start of transaction `i` in `bytes`.
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TxOpaque {
     offsets: Vec<usize>,
@@ -25,17 +80,92 @@ impl<'a> Iterator for TransactionsOpaqueIter<'a> {
 }
 
 impl TxOpaque {
+    pub fn try_from_iter<I>(txs: I) -> Result<Self, TxOpaqueError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut tx_opaque = Self::default();
+        for tx in txs {
+            tx_opaque.push(tx.as_ref())?;
+        }
+        Ok(tx_opaque)
+    }
+
+    pub fn push(&mut self, tx: &[u8]) -> Result<(), TxOpaqueError> {
+        if self.offsets.len() >= MAX_TRANSACTIONS_PER_PAYLOAD {
+            return Err(TxOpaqueError::TooManyTransactions {
+                limit: MAX_TRANSACTIONS_PER_PAYLOAD,
+            });
+        }
+        if tx.len() > MAX_BYTES_PER_TRANSACTION {
+            return Err(TxOpaqueError::TransactionTooLarge {
+                len: tx.len(),
+                limit: MAX_BYTES_PER_TRANSACTION,
+            });
+        }
+        let encoded_len = self.ssz_bytes_len() + BYTES_PER_LENGTH_OFFSET + tx.len();
+        if encoded_len > u32::MAX as usize {
+            return Err(TxOpaqueError::OffsetOverflow { len: encoded_len });
+        }
+        self.offsets.push(self.bytes.len());
+        self.bytes.extend_from_slice(tx);
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let start = *self.offsets.get(index)?;
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.bytes.len());
+        self.bytes.get(start..end)
+    }
+
+    /// Number of transactions.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Combined size of all transactions, without the offset table.
+    pub fn total_bytes(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Keeps the first `len` transactions, like [`Vec::truncate`].
+    pub fn truncate(&mut self, len: usize) {
+        if let Some(&end) = self.offsets.get(len) {
+            self.offsets.truncate(len);
+            self.bytes.truncate(end);
+        }
+    }
+
     pub fn iter<'a>(&'a self) -> TransactionsOpaqueIter<'a> {
         TransactionsOpaqueIter {
             offsets: &self.offsets,
             bytes: &self.bytes,
         }
     }
+
     fn len_offset_bytes(&self) -> usize {
         self.offsets.len().saturating_mul(BYTES_PER_LENGTH_OFFSET)
     }
 }
 
+impl<'a> IntoIterator for &'a TxOpaque {
+    type Item = &'a [u8];
+    type IntoIter = TransactionsOpaqueIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl SszEncode for TxOpaque {
     fn is_ssz_static() -> bool {
         false
@@ -47,7 +177,7 @@ impl SszEncode for TxOpaque {
 
     fn ssz_max_len() -> usize {
         // max size of tx (in bytes) times max size of tx list
-        1073741824 * 1048576
+        MAX_BYTES_PER_TRANSACTION * MAX_TRANSACTIONS_PER_PAYLOAD
     }
 
     fn ssz_bytes_len(&self) -> usize {
@@ -84,71 +214,526 @@ impl SszDecode for TxOpaque {
     }
 
     fn ssz_max_len() -> usize {
-        1073741824 * 1048576
+        MAX_BYTES_PER_TRANSACTION * MAX_TRANSACTIONS_PER_PAYLOAD
     }
 
+    /// Validated like the zero-copy views, so a short or inconsistent offset
+    /// table is an error rather than an out-of-bounds slice.
     fn ssz_read(
         _fixed_bytes: &mut impl Buf,
         variable_bytes: &mut impl Buf,
     ) -> Result<Self, DecodeError> {
-        if !variable_bytes.has_remaining() {
-            return Ok(Self::default());
-        }
-        let (offset_bytes, value_bytes) = {
-            let first_offset =
-                read_offset_from_slice(&variable_bytes.chunk()[0..BYTES_PER_LENGTH_OFFSET])?;
-            if first_offset % BYTES_PER_LENGTH_OFFSET != 0 || first_offset < BYTES_PER_LENGTH_OFFSET
-            {
-                return Err(DecodeError::InvalidListFixedBytesLen(first_offset));
-            }
-            variable_bytes
-                .chunk()
-                .split_at_checked(first_offset)
-                .ok_or(DecodeError::OffsetOutOfBounds(first_offset))?
-        };
-        // Disallow lists that have too many transactions.
-        let num_items = offset_bytes.len() / BYTES_PER_LENGTH_OFFSET;
-        let max_tx_count = 1048576;
-        if num_items > max_tx_count {
+        TxOpaqueRef::from_ssz_bytes(variable_bytes.chunk()).map(Self::from)
+    }
+}
+
+impl TreeHash for TxOpaque {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::List
+    }
+
+    fn tree_hash_packed_encoding(&self) -> PackedEncoding {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("List should never be packed.")
+    }
+
+    /// Hashed as the spec's `List[ByteList[MAX_BYTES_PER_TRANSACTION], MAX_TRANSACTIONS_PER_PAYLOAD]`,
+    /// merkleizing each transaction straight out of the flat buffer.
+    fn tree_hash_root(&self) -> Hash256 {
+        transactions_root(self.iter(), self.len())
+    }
+}
+
+fn transactions_root<'a>(txs: impl Iterator<Item = &'a [u8]>, len: usize) -> Hash256 {
+    let tx_chunks = MAX_BYTES_PER_TRANSACTION.div_ceil(BYTES_PER_CHUNK);
+    let mut hasher = MerkleHasher::with_leaves(MAX_TRANSACTIONS_PER_PAYLOAD);
+    for tx in txs {
+        let tx_root = mix_in_length(&merkle_root(tx, tx_chunks), tx.len());
+        hasher
+            .write(tx_root.as_slice())
+            .expect("decode bounds the number of transactions");
+    }
+    let root = hasher
+        .finish()
+        .expect("decode bounds the number of transactions");
+    mix_in_length(&root, len)
+}
+
+/// Checks an encoded transaction list's offset table once, so the zero-copy
+/// views can read offsets back without re-validating them. Returns the number
+/// of transactions.
+fn validate_offsets(bytes: &[u8]) -> Result<usize, DecodeError> {
+    let Some(first_offset) = bytes.get(..BYTES_PER_LENGTH_OFFSET) else {
+        if bytes.is_empty() {
+            return Ok(0);
+        }
+        return Err(DecodeError::InvalidByteLength {
+            len: bytes.len(),
+            expected: BYTES_PER_LENGTH_OFFSET,
+        });
+    };
+    let first_offset = read_offset_from_slice(first_offset)?;
+    if first_offset % BYTES_PER_LENGTH_OFFSET != 0 || first_offset < BYTES_PER_LENGTH_OFFSET {
+        return Err(DecodeError::InvalidListFixedBytesLen(first_offset));
+    }
+    if first_offset > bytes.len() {
+        return Err(DecodeError::OffsetOutOfBounds(first_offset));
+    }
+    let num_items = first_offset / BYTES_PER_LENGTH_OFFSET;
+    if num_items > MAX_TRANSACTIONS_PER_PAYLOAD {
+        return Err(DecodeError::BytesInvalid(format!(
+            "List of {num_items} txs exceeds maximum of {MAX_TRANSACTIONS_PER_PAYLOAD}"
+        )));
+    }
+    // each transaction ends where the next starts, the last one at the end of the input
+    let ends = bytes[BYTES_PER_LENGTH_OFFSET..first_offset]
+        .chunks_exact(BYTES_PER_LENGTH_OFFSET)
+        .map(read_offset_from_slice)
+        .chain(std::iter::once(Ok(bytes.len())));
+    let mut start = first_offset;
+    for end in ends {
+        let end = end?;
+        if end > bytes.len() {
+            return Err(DecodeError::OffsetOutOfBounds(end));
+        }
+        let tx_len = end
+            .checked_sub(start)
+            .ok_or(DecodeError::OffsetsAreDecreasing(end))?;
+        if tx_len > MAX_BYTES_PER_TRANSACTION {
             return Err(DecodeError::BytesInvalid(format!(
-                "List of {} txs exceeds maximum of {:?}",
-                num_items, max_tx_count
+                "length of {tx_len} exceeds maximum tx length of {MAX_BYTES_PER_TRANSACTION}",
             )));
         }
-        let max_tx_bytes = 1073741824;
-        let mut offsets = Vec::with_capacity(num_items);
-        let mut offset_iter = offset_bytes.chunks(BYTES_PER_LENGTH_OFFSET).peekable();
-        while let Some(offset) = offset_iter.next() {
-            let offset = read_offset_from_slice(offset)?;
-            // Make the offset assume that the values start at index 0, rather
-            // than following the offset bytes.
-            let offset = offset
-                .checked_sub(offset_bytes.len())
-                .ok_or(DecodeError::OffsetIntoFixedPortion(offset))?;
-            let next_offset = offset_iter
-                .peek()
-                .copied()
-                .map(read_offset_from_slice)
-                .unwrap_or(Ok(value_bytes.len()))?;
-            // Disallow any offset that is lower than the previous.
-            let tx_len = next_offset
-                .checked_sub(offset)
-                .ok_or(DecodeError::OffsetsAreDecreasing(offset))?;
-            // Disallow transactions that are too large.
-            if tx_len > max_tx_bytes {
-                return Err(DecodeError::BytesInvalid(format!(
-                    "length of {tx_len} exceeds maximum tx length of {max_tx_bytes}",
-                )));
-            }
-            // Disallow an offset that points outside of the value bytes.
-            if offset > value_bytes.len() {
-                return Err(DecodeError::OffsetOutOfBounds(offset));
+        start = end;
+    }
+    Ok(num_items)
+}
+
+/// A transaction list borrowed from its SSZ encoding. The offsets are
+/// validated once on construction and read from the input on access, so
+/// decoding neither copies the transactions nor allocates an offset table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TxOpaqueRef<'a> {
+    bytes: &'a [u8],
+    len: usize,
+}
+
+impl<'a> TxOpaqueRef<'a> {
+    pub fn from_ssz_bytes(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let len = validate_offsets(bytes)?;
+        Ok(Self { bytes, len })
+    }
+
+    /// Start of transaction `index` in the encoding, or its end for `index == len`.
+    fn offset(&self, index: usize) -> usize {
+        if index == self.len {
+            return self.bytes.len();
+        }
+        let start = index * BYTES_PER_LENGTH_OFFSET;
+        read_offset_from_slice(&self.bytes[start..start + BYTES_PER_LENGTH_OFFSET])
+            .expect("offsets are validated on construction")
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+        if index >= self.len {
+            return None;
+        }
+        self.bytes.get(self.offset(index)..self.offset(index + 1))
+    }
+
+    /// Number of transactions.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Combined size of all transactions, without the offset table.
+    pub fn total_bytes(&self) -> usize {
+        self.bytes.len() - self.len * BYTES_PER_LENGTH_OFFSET
+    }
+
+    /// The SSZ encoding this view borrows.
+    pub fn as_ssz_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn iter(&self) -> TxOpaqueRefIter<'a> {
+        TxOpaqueRefIter {
+            txs: *self,
+            index: 0,
+        }
+    }
+}
+
+pub struct TxOpaqueRefIter<'a> {
+    txs: TxOpaqueRef<'a>,
+    index: usize,
+}
+
+impl<'a> Iterator for TxOpaqueRefIter<'a> {
+    type Item = &'a [u8];
+    fn next(&mut self) -> Option<Self::Item> {
+        let tx = self.txs.get(self.index)?;
+        self.index += 1;
+        Some(tx)
+    }
+}
+
+impl<'a> IntoIterator for TxOpaqueRef<'a> {
+    type Item = &'a [u8];
+    type IntoIter = TxOpaqueRefIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<TxOpaqueRef<'_>> for TxOpaque {
+    fn from(txs: TxOpaqueRef<'_>) -> Self {
+        let fixed_len = txs.len * BYTES_PER_LENGTH_OFFSET;
+        Self {
+            offsets: (0..txs.len).map(|i| txs.offset(i) - fixed_len).collect(),
+            bytes: txs.bytes[fixed_len..].to_vec(),
+        }
+    }
+}
+
+impl SszEncode for TxOpaqueRef<'_> {
+    fn is_ssz_static() -> bool {
+        false
+    }
+
+    fn ssz_fixed_len() -> usize {
+        BYTES_PER_LENGTH_OFFSET
+    }
+
+    fn ssz_max_len() -> usize {
+        MAX_BYTES_PER_TRANSACTION * MAX_TRANSACTIONS_PER_PAYLOAD
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn ssz_write_fixed(&self, offset: &mut usize, buf: &mut impl BufMut) {
+        buf.put_slice(&offset.to_le_bytes()[0..BYTES_PER_LENGTH_OFFSET]);
+        *offset += self.ssz_bytes_len();
+    }
+
+    fn ssz_write_variable(&self, buf: &mut impl BufMut) {
+        self.ssz_write(buf);
+    }
+
+    fn ssz_write(&self, buf: &mut impl BufMut) {
+        buf.put_slice(self.bytes);
+    }
+}
+
+impl TreeHash for TxOpaqueRef<'_> {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::List
+    }
+
+    fn tree_hash_packed_encoding(&self) -> PackedEncoding {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_root(&self) -> Hash256 {
+        transactions_root(self.iter(), self.len)
+    }
+}
+
+/// [`TxOpaqueRef`] over a shared [`Bytes`] buffer, for keeping a decoded list
+/// around without copying it or borrowing from whoever owns the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxOpaqueBytes {
+    bytes: Bytes,
+    len: usize,
+}
+
+impl TxOpaqueBytes {
+    pub fn from_ssz_bytes(bytes: Bytes) -> Result<Self, DecodeError> {
+        let len = validate_offsets(&bytes)?;
+        Ok(Self { bytes, len })
+    }
+
+    pub fn as_tx_ref(&self) -> TxOpaqueRef<'_> {
+        TxOpaqueRef {
+            bytes: &self.bytes,
+            len: self.len,
+        }
+    }
+
+    /// Transaction `index` as a slice sharing this buffer.
+    pub fn get(&self, index: usize) -> Option<Bytes> {
+        let txs = self.as_tx_ref();
+        if index >= txs.len {
+            return None;
+        }
+        Some(self.bytes.slice(txs.offset(index)..txs.offset(index + 1)))
+    }
+
+    /// Number of transactions.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl From<&TxOpaque> for Transactions {
+    fn from(txs: &TxOpaque) -> Self {
+        // TxOpaque enforces the same limits, so nothing is truncated
+        let txs: Vec<Transaction> = txs.iter().map(|tx| tx.to_vec().into()).collect();
+        txs.into()
+    }
+}
+
+impl TryFrom<&Transactions> for TxOpaque {
+    type Error = TxOpaqueError;
+
+    fn try_from(txs: &Transactions) -> Result<Self, Self::Error> {
+        Self::try_from_iter(txs.iter().map(|tx| &tx[..]))
+    }
+}
+
+impl<E: EthSpec> TryFrom<&TransactionsOpaque<E>> for TxOpaque {
+    type Error = TxOpaqueError;
+
+    fn try_from(txs: &TransactionsOpaque<E>) -> Result<Self, Self::Error> {
+        Self::try_from_iter(txs.iter())
+    }
+}
+
+/// Goes through SSZ, which both sides lay out identically, since lighthouse
+/// only builds a `TransactionsOpaque` by decoding.
+impl<E: EthSpec> TryFrom<&TxOpaque> for TransactionsOpaque<E> {
+    type Error = ssz::DecodeError;
+
+    fn try_from(txs: &TxOpaque) -> Result<Self, Self::Error> {
+        <Self as ssz::Decode>::from_ssz_bytes(&txs.to_ssz())
+    }
+}
+
+impl<N, M> TryFrom<&ContiguousList<ByteList<N>, M>> for TxOpaque {
+    type Error = TxOpaqueError;
+
+    fn try_from(txs: &ContiguousList<ByteList<N>, M>) -> Result<Self, Self::Error> {
+        Self::try_from_iter(txs.iter().map(|tx| &tx[..]))
+    }
+}
+
+/// Goes through SSZ, like the lighthouse conversion.
+impl<N, M> TryFrom<&TxOpaque> for ContiguousList<ByteList<N>, M>
+where
+    Self: SszReadDefault,
+{
+    type Error = ReadError;
+
+    fn try_from(txs: &TxOpaque) -> Result<Self, Self::Error> {
+        Self::from_ssz_default(txs.to_ssz())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx_opaque(txs: &[Vec<u8>]) -> TxOpaque {
+        TxOpaque::try_from_iter(txs).expect("within the spec's limits")
+    }
+
+    /// Both flat-buffer hashers must agree with hashing the spec's list of
+    /// byte lists element by element.
+    fn check_root(txs: &[Vec<u8>]) {
+        let transactions: Transactions = txs
+            .iter()
+            .map(|tx| Transaction::from(tx.clone()))
+            .collect::<Vec<_>>()
+            .into();
+        let expected = transactions.tree_hash_root();
+
+        let txs = tx_opaque(txs);
+        assert_eq!(txs.tree_hash_root(), expected);
+        let bytes = txs.to_ssz();
+        let txs = TxOpaqueRef::from_ssz_bytes(&bytes).expect("TxOpaque encodes validly");
+        assert_eq!(txs.tree_hash_root(), expected);
+    }
+
+    #[test]
+    fn empty_list_root() {
+        check_root(&[]);
+    }
+
+    #[test]
+    fn zero_length_transaction_root() {
+        check_root(&[vec![]]);
+    }
+
+    #[test]
+    fn chunk_boundary_roots() {
+        check_root(&[vec![0xaa; 32]]);
+        check_root(&[vec![0xaa; 33]]);
+    }
+
+    #[test]
+    fn multi_transaction_root() {
+        check_root(&[vec![1], vec![], vec![2; 32], vec![3; 33], vec![4; 100]]);
+    }
+
+    #[test]
+    fn push_past_transaction_limit_fails_unchanged() {
+        let mut txs = TxOpaque::try_from_iter(
+            std::iter::repeat(Vec::<u8>::new()).take(MAX_TRANSACTIONS_PER_PAYLOAD),
+        )
+        .expect("exactly at the limit");
+        let before = txs.clone();
+        assert_eq!(
+            txs.push(b"tx"),
+            Err(TxOpaqueError::TooManyTransactions {
+                limit: MAX_TRANSACTIONS_PER_PAYLOAD
+            })
+        );
+        assert_eq!(txs, before);
+    }
+
+    #[test]
+    fn push_oversized_transaction_fails_unchanged() {
+        let mut txs = tx_opaque(&[vec![1, 2]]);
+        let before = txs.clone();
+        let oversized = vec![0; MAX_BYTES_PER_TRANSACTION + 1];
+        let expected = TxOpaqueError::TransactionTooLarge {
+            len: MAX_BYTES_PER_TRANSACTION + 1,
+            limit: MAX_BYTES_PER_TRANSACTION,
+        };
+        assert_eq!(txs.push(&oversized), Err(expected.clone()));
+        assert_eq!(txs, before);
+        assert_eq!(TxOpaque::try_from_iter([&oversized]), Err(expected));
+    }
+
+    #[test]
+    fn get_past_the_end_is_none() {
+        let txs = tx_opaque(&[vec![1], vec![]]);
+        assert_eq!(txs.get(0), Some(&[1][..]));
+        assert_eq!(txs.get(1), Some(&[][..]));
+        assert_eq!(txs.get(txs.len()), None);
+        assert_eq!(TxOpaque::default().get(0), None);
+    }
+
+    #[test]
+    fn truncate_past_len_is_a_no_op() {
+        let mut txs = tx_opaque(&[vec![1], vec![2, 3]]);
+        let before = txs.clone();
+        txs.truncate(2);
+        assert_eq!(txs, before);
+        txs.truncate(5);
+        assert_eq!(txs, before);
+    }
+
+    #[test]
+    fn truncate_keeps_bytes_and_encoding_consistent() {
+        let all = [vec![1], vec![], vec![2, 3], vec![4, 5, 6]];
+        for len in 0..all.len() {
+            let mut txs = tx_opaque(&all);
+            txs.truncate(len);
+            let kept = tx_opaque(&all[..len]);
+            assert_eq!(txs.len(), len);
+            assert_eq!(
+                txs.total_bytes(),
+                all[..len].iter().map(Vec::len).sum::<usize>()
+            );
+            assert_eq!(txs.to_ssz(), kept.to_ssz());
+            assert_eq!(txs, kept);
+        }
+    }
+
+    /// Both views must reject `bytes` with `expected`.
+    fn check_rejected(bytes: &[u8], expected: DecodeError) {
+        let expected = format!("{expected:?}");
+        let e = TxOpaqueRef::from_ssz_bytes(bytes).expect_err("invalid offsets");
+        assert_eq!(format!("{e:?}"), expected, "TxOpaqueRef");
+        let e = TxOpaqueBytes::from_ssz_bytes(Bytes::copy_from_slice(bytes))
+            .expect_err("invalid offsets");
+        assert_eq!(format!("{e:?}"), expected, "TxOpaqueBytes");
+    }
+
+    fn offsets(offsets: &[u32], tail: &[u8]) -> Vec<u8> {
+        let mut bytes: Vec<u8> = offsets.iter().flat_map(|o| o.to_le_bytes()).collect();
+        bytes.extend_from_slice(tail);
+        bytes
+    }
+
+    #[test]
+    fn rejects_short_input() {
+        check_rejected(
+            &[4, 0, 0],
+            DecodeError::InvalidByteLength {
+                len: 3,
+                expected: BYTES_PER_LENGTH_OFFSET,
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_unaligned_first_offset() {
+        check_rejected(
+            &offsets(&[5], &[1]),
+            DecodeError::InvalidListFixedBytesLen(5),
+        );
+        check_rejected(
+            &offsets(&[0], &[]),
+            DecodeError::InvalidListFixedBytesLen(0),
+        );
+    }
+
+    #[test]
+    fn rejects_decreasing_offsets() {
+        check_rejected(
+            &offsets(&[12, 10, 14], &[1, 2]),
+            DecodeError::OffsetsAreDecreasing(10),
+        );
+    }
+
+    #[test]
+    fn rejects_offsets_past_the_end() {
+        check_rejected(&offsets(&[8], &[]), DecodeError::OffsetOutOfBounds(8));
+        check_rejected(&offsets(&[8, 20], &[1]), DecodeError::OffsetOutOfBounds(20));
+    }
+
+    #[test]
+    fn views_agree_with_tx_opaque() {
+        let all = [vec![1], vec![], vec![2; 32], vec![3; 33]];
+        for len in 0..=all.len() {
+            let bytes = tx_opaque(&all[..len]).to_ssz();
+            let owned = <TxOpaque as SszDecode>::from_ssz_bytes(&bytes).expect("valid encoding");
+            let borrowed = TxOpaqueRef::from_ssz_bytes(&bytes).expect("valid encoding");
+            let shared =
+                TxOpaqueBytes::from_ssz_bytes(Bytes::from(bytes.clone())).expect("valid encoding");
+
+            assert_eq!(owned.len(), len);
+            assert_eq!(borrowed.len(), len);
+            assert_eq!(shared.len(), len);
+            for index in 0..=len {
+                let expected = all.get(index).filter(|_| index < len).map(Vec::as_slice);
+                assert_eq!(owned.get(index), expected);
+                assert_eq!(borrowed.get(index), expected);
+                assert_eq!(shared.get(index).as_deref(), expected);
             }
-            offsets.push(offset);
+            assert_eq!(owned.to_ssz(), bytes);
+            assert_eq!(borrowed.to_ssz(), bytes);
+            assert_eq!(shared.as_tx_ref().to_ssz(), bytes);
         }
-        Ok(Self {
-            offsets,
-            bytes: value_bytes.to_vec(),
-        })
     }
 }
@@ -0,0 +1,321 @@
+use std::fmt;
+
+use alloy_primitives::B256;
+use sszb::SszEncode;
+use tree_hash::TreeHash;
+
+use crate::schema::{self, Schema};
+use crate::{
+    peek_slot, ArenaSpec, BeaconStateAnyFork, FixtureKind, ForkName, SignedBeaconBlockAnyFork,
+};
+
+/// Every enabled library decoded, re-encoded and hashed the fixture identically.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verified {
+    pub fork: ForkName,
+    pub libraries: Vec<&'static str>,
+    pub root: B256,
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    /// The fixture is too short to carry a slot, so its fork is unknown.
+    NoSlot(FixtureKind),
+    Decode {
+        library: &'static str,
+        reason: String,
+    },
+    /// Re-encoding or hashing a decoded value failed.
+    Encode {
+        library: &'static str,
+        reason: String,
+    },
+    /// The re-encoding differs from the input, first at byte `offset`.
+    Bytes {
+        library: &'static str,
+        offset: usize,
+        path: String,
+    },
+    /// The roots differ; `path` is the first field whose own root differs
+    /// from the spec's, when the library exposes its field roots.
+    Root {
+        library: &'static str,
+        root: B256,
+        reference: &'static str,
+        expected: B256,
+        path: Option<String>,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSlot(kind) => {
+                write!(f, "{} fixture too short to contain a slot", kind.as_str())
+            }
+            Self::Decode { library, reason } => write!(f, "{library} failed to decode: {reason}"),
+            Self::Encode { library, reason } => {
+                write!(f, "{library} failed to re-encode or hash: {reason}")
+            }
+            Self::Bytes {
+                library,
+                offset,
+                path,
+            } => {
+                let path = if path.is_empty() { "<top level>" } else { path };
+                write!(
+                    f,
+                    "{library} re-encoding differs from the input at byte {offset}, in {path}"
+                )
+            }
+            Self::Root {
+                library,
+                root,
+                reference,
+                expected,
+                path,
+            } => {
+                write!(
+                    f,
+                    "{library} hash_tree_root {root} differs from {reference}'s {expected}"
+                )?;
+                match path {
+                    Some(path) => write!(f, ", first in {path}"),
+                    None => write!(f, ", {library} exposes no field roots to narrow it down"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// One library's decode, re-encode and hash of the fixture.
+struct RoundTrip {
+    library: &'static str,
+    bytes: Vec<u8>,
+    root: B256,
+    /// Roots of the fields of the container at the `leaves_at` path passed
+    /// to [`check`], if the library hands them out.
+    leaves: Option<Vec<B256>>,
+}
+
+/// Where [`RoundTrip::leaves`] sit in a block: lighthouse only exposes the
+/// body's field roots.
+const BLOCK_LEAVES_AT: &[&str] = &["message", "body"];
+
+fn decode_error(library: &'static str, e: impl fmt::Debug) -> VerifyError {
+    VerifyError::Decode {
+        library,
+        reason: format!("{e:?}"),
+    }
+}
+
+#[cfg(any(feature = "sigp", feature = "grandine"))]
+fn encode_error(library: &'static str, e: impl fmt::Debug) -> VerifyError {
+    VerifyError::Encode {
+        library,
+        reason: format!("{e:?}"),
+    }
+}
+
+/// Round-trips a `SignedBeaconBlock` through sszb and, when their features
+/// are enabled, lighthouse and grandine.
+pub fn verify_block<S: ArenaSpec>(bytes: &[u8]) -> Result<Verified, VerifyError> {
+    let slot =
+        peek_slot(FixtureKind::Block, bytes).ok_or(VerifyError::NoSlot(FixtureKind::Block))?;
+    let fork = S::NETWORK.fork_at_slot(slot);
+    let mut trips = Vec::new();
+
+    let block = SignedBeaconBlockAnyFork::<S::Preset>::from_ssz_bytes_for_fork(bytes, fork)
+        .map_err(|e| decode_error("sszb", e))?;
+    trips.push(RoundTrip {
+        library: "sszb",
+        bytes: block.to_ssz(),
+        root: block.tree_hash_root(),
+        // schema.rs's tests pin sszb's roots to the spec's field by field
+        leaves: None,
+    });
+
+    #[cfg(feature = "sigp")]
+    {
+        use ssz::Encode;
+
+        let block =
+            sigp_types::SignedBeaconBlock::<S::Sigp>::from_ssz_bytes_for_fork(bytes, fork.into())
+                .map_err(|e| decode_error("lighthouse", e))?;
+        trips.push(RoundTrip {
+            library: "lighthouse",
+            bytes: block.as_ssz_bytes(),
+            root: B256::from(block.tree_hash_root().0),
+            leaves: Some(
+                block
+                    .message()
+                    .body()
+                    .body_merkle_leaves()
+                    .into_iter()
+                    .map(|leaf| B256::from(leaf.0))
+                    .collect(),
+            ),
+        });
+    }
+
+    #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        use grandine_ssz::{SszHash, SszRead, SszWrite};
+
+        let block = grandine_types::combined::SignedBeaconBlock::<S::Grandine>::from_ssz(
+            &S::grandine_config(),
+            bytes,
+        )
+        .map_err(|e| decode_error("grandine", e))?;
+        trips.push(RoundTrip {
+            library: "grandine",
+            bytes: block.to_ssz().map_err(|e| encode_error("grandine", e))?,
+            root: B256::from(block.hash_tree_root().0),
+            leaves: None,
+        });
+    }
+
+    check(
+        bytes,
+        &schema::signed_beacon_block::<S::Preset>(fork),
+        BLOCK_LEAVES_AT,
+        fork,
+        trips,
+    )
+}
+
+/// Round-trips a `BeaconState` the same way as [`verify_block`].
+pub fn verify_state<S: ArenaSpec>(bytes: &[u8]) -> Result<Verified, VerifyError> {
+    let slot =
+        peek_slot(FixtureKind::State, bytes).ok_or(VerifyError::NoSlot(FixtureKind::State))?;
+    let fork = S::NETWORK.fork_at_slot(slot);
+    let mut trips = Vec::new();
+
+    let state = BeaconStateAnyFork::<S::Preset>::from_ssz_bytes_for_fork(bytes, fork)
+        .map_err(|e| decode_error("sszb", e))?;
+    trips.push(RoundTrip {
+        library: "sszb",
+        bytes: state.to_ssz(),
+        root: state.tree_hash_root(),
+        leaves: None,
+    });
+
+    #[cfg(feature = "sigp")]
+    {
+        use ssz::Encode;
+
+        let mut state = sigp_types::BeaconState::<S::Sigp>::from_ssz_bytes(bytes, &S::sigp_spec())
+            .map_err(|e| decode_error("lighthouse", e))?;
+        let root = state
+            .update_tree_hash_cache()
+            .map_err(|e| encode_error("lighthouse", e))?;
+        trips.push(RoundTrip {
+            library: "lighthouse",
+            bytes: state.as_ssz_bytes(),
+            root: B256::from(root.0),
+            leaves: Some(
+                state
+                    .get_beacon_state_leaves()
+                    .into_iter()
+                    .map(|leaf| B256::from(leaf.0))
+                    .collect(),
+            ),
+        });
+    }
+
+    #[cfg(feature = "grandine")]
+    if S::GRANDINE_SUPPORTED {
+        use grandine_ssz::{SszHash, SszRead, SszWrite};
+
+        let state = grandine_types::combined::BeaconState::<S::Grandine>::from_ssz(
+            &S::grandine_config(),
+            bytes,
+        )
+        .map_err(|e| decode_error("grandine", e))?;
+        trips.push(RoundTrip {
+            library: "grandine",
+            bytes: state.to_ssz().map_err(|e| encode_error("grandine", e))?,
+            root: B256::from(state.hash_tree_root().0),
+            leaves: None,
+        });
+    }
+
+    check(
+        bytes,
+        &schema::beacon_state::<S::Preset>(fork),
+        &[],
+        fork,
+        trips,
+    )
+}
+
+/// Every re-encoding must reproduce the input exactly and every root must
+/// match the first library's.
+fn check(
+    input: &[u8],
+    schema: &Schema,
+    leaves_at: &[&str],
+    fork: ForkName,
+    trips: Vec<RoundTrip>,
+) -> Result<Verified, VerifyError> {
+    for trip in &trips {
+        if trip.bytes != input {
+            let offset = input
+                .iter()
+                .zip(&trip.bytes)
+                .position(|(a, b)| a != b)
+                .unwrap_or(input.len().min(trip.bytes.len()));
+            return Err(VerifyError::Bytes {
+                library: trip.library,
+                offset,
+                path: schema.path_at(input, offset),
+            });
+        }
+    }
+
+    let (reference, others) = trips.split_first().expect("sszb always takes part");
+    if let Some(trip) = others.iter().find(|trip| trip.root != reference.root) {
+        return Err(VerifyError::Root {
+            library: trip.library,
+            root: trip.root,
+            reference: reference.library,
+            expected: reference.root,
+            path: first_differing_field(input, schema, leaves_at, trip),
+        });
+    }
+
+    Ok(Verified {
+        fork,
+        libraries: trips.iter().map(|trip| trip.library).collect(),
+        root: reference.root,
+    })
+}
+
+/// The re-encodings match, so a root mismatch comes from hashing. Compares
+/// the library's field roots with the spec's, as hashed by `schema` from the
+/// input, and names the first field that differs.
+fn first_differing_field(
+    input: &[u8],
+    schema: &Schema,
+    leaves_at: &[&str],
+    trip: &RoundTrip,
+) -> Option<String> {
+    let leaves = trip.leaves.as_ref()?;
+    let (container, bytes) = schema.descend(input, leaves_at)?;
+    let fields = container.field_roots(bytes)?;
+    let mut path: String = leaves_at.iter().map(|name| format!(".{name}")).collect();
+    if fields.len() != leaves.len() {
+        // a different field count is itself the difference
+        return Some(path);
+    }
+    let (name, _) = fields
+        .iter()
+        .zip(leaves)
+        .find(|((_, spec), leaf)| spec != *leaf)?
+        .0;
+    path.push('.');
+    path.push_str(name);
+    Some(path)
+}
@@ -0,0 +1,262 @@
+//! Runs the arena's containers against the consensus-spec-tests `ssz_static`
+//! vectors. Point `CONSENSUS_SPEC_TESTS_DIR` at an extracted release, the
+//! directory holding `tests/`, e.g.
+//!
+//! ```sh
+//! CONSENSUS_SPEC_TESTS_DIR=~/consensus-spec-tests cargo test --test ssz_static -- --ignored --nocapture
+//! ```
+//!
+//! The tests are ignored by default since they need the vectors on disk, and
+//! fail rather than pass vacuously when run without the variable.
+//!
+//! Each case is decoded from `serialized.ssz_snappy`, re-encoded and
+//! compared byte for byte, and hashed against `roots.yaml`. The containers
+//! don't implement serde, so `value.yaml` is covered by the byte-exact
+//! round trip rather than compared field by field. Spec types the arena
+//! doesn't model are listed per fork at the end of the run.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use alloy_primitives::B256;
+use sszb::{SszDecode, SszEncode};
+use tree_hash::TreeHash;
+
+use ssz_arena::*;
+
+fn check_case<T: SszDecode + SszEncode + TreeHash>(case: &Path) -> Result<(), String> {
+    let compressed = fs::read(case.join("serialized.ssz_snappy")).map_err(|e| e.to_string())?;
+    let bytes = snap::raw::Decoder::new()
+        .decompress_vec(&compressed)
+        .map_err(|e| format!("snappy: {e}"))?;
+    let roots = fs::read_to_string(case.join("roots.yaml")).map_err(|e| e.to_string())?;
+    let roots: serde_yaml::Value =
+        serde_yaml::from_str(&roots).map_err(|e| format!("roots.yaml: {e}"))?;
+    let expected: B256 = roots["root"]
+        .as_str()
+        .ok_or("roots.yaml has no root")?
+        .parse()
+        .map_err(|e| format!("roots.yaml: {e}"))?;
+
+    let value = <T as SszDecode>::from_ssz_bytes(&bytes).map_err(|e| format!("decode: {e:?}"))?;
+    let encoded = value.to_ssz();
+    if encoded != bytes {
+        let offset = encoded
+            .iter()
+            .zip(&bytes)
+            .position(|(a, b)| a != b)
+            .unwrap_or(encoded.len().min(bytes.len()));
+        return Err(format!(
+            "encode: differs from serialized.ssz_snappy at byte {offset}"
+        ));
+    }
+    let root = value.tree_hash_root();
+    if root != expected {
+        return Err(format!("root: got {root}, expected {expected}"));
+    }
+    Ok(())
+}
+
+/// `None` if the arena has no container for `type_name` in `fork`.
+macro_rules! dispatch {
+    ($type_name:expr, $case:expr, { $($name:literal => $ty:ty),* $(,)? }) => {
+        match $type_name {
+            $($name => Some(check_case::<$ty>($case)),)*
+            _ => None,
+        }
+    };
+}
+
+fn run_case<P: Preset>(fork: ForkName, type_name: &str, case: &Path) -> Option<Result<(), String>> {
+    // containers whose layout is the same in every fork that has them
+    let shared = dispatch!(type_name, case, {
+        "AttestationData" => AttestationData,
+        "BeaconBlockHeader" => BeaconBlockHeader,
+        "BLSToExecutionChange" => BlsToExecutionChange,
+        "Checkpoint" => Checkpoint,
+        "ConsolidationRequest" => ConsolidationRequest,
+        "Deposit" => Deposit,
+        "DepositData" => DepositData,
+        "DepositRequest" => DepositRequest,
+        "Eth1Data" => Eth1Data,
+        "ExecutionRequests" => ExecutionRequests<P>,
+        "Fork" => Fork,
+        "HistoricalSummary" => HistoricalSummary,
+        "PendingAttestation" => PendingAttestation,
+        "PendingConsolidation" => PendingConsolidation,
+        "PendingDeposit" => PendingDeposit,
+        "PendingPartialWithdrawal" => PendingPartialWithdrawal,
+        "ProposerSlashing" => ProposerSlashing,
+        "SignedBeaconBlockHeader" => SignedBeaconBlockHeader,
+        "SignedBLSToExecutionChange" => SignedBlsToExecutionChange,
+        "SignedVoluntaryExit" => SignedVoluntaryExit,
+        "SyncAggregate" => SyncAggregate<P>,
+        "SyncCommittee" => SyncCommittee<P>,
+        "Validator" => Validator,
+        "VoluntaryExit" => VoluntaryExit,
+        "Withdrawal" => Withdrawal,
+        "WithdrawalRequest" => WithdrawalRequest,
+    });
+    if shared.is_some() {
+        return shared;
+    }
+
+    let attestations = match fork {
+        ForkName::Electra => dispatch!(type_name, case, {
+            "Attestation" => AttestationElectra<P>,
+            "AttesterSlashing" => AttesterSlashingElectra<P>,
+            "IndexedAttestation" => IndexedAttestationElectra<P>,
+        }),
+        _ => dispatch!(type_name, case, {
+            "Attestation" => Attestation,
+            "AttesterSlashing" => AttesterSlashing,
+            "IndexedAttestation" => IndexedAttestation,
+        }),
+    };
+    if attestations.is_some() {
+        return attestations;
+    }
+
+    match fork {
+        ForkName::Phase0 => dispatch!(type_name, case, {
+            "BeaconBlock" => BeaconBlockPhase0,
+            "BeaconBlockBody" => BeaconBlockBodyPhase0,
+            "BeaconState" => BeaconStatePhase0<P>,
+            "SignedBeaconBlock" => SignedBeaconBlockPhase0,
+        }),
+        ForkName::Altair => dispatch!(type_name, case, {
+            "BeaconBlock" => BeaconBlockAltair<P>,
+            "BeaconBlockBody" => BeaconBlockBodyAltair<P>,
+            "BeaconState" => BeaconStateAltair<P>,
+            "SignedBeaconBlock" => SignedBeaconBlockAltair<P>,
+        }),
+        ForkName::Bellatrix => dispatch!(type_name, case, {
+            "BeaconBlock" => BeaconBlockBellatrix<P>,
+            "BeaconBlockBody" => BeaconBlockBodyBellatrix<P>,
+            "BeaconState" => BeaconStateBellatrix<P>,
+            "ExecutionPayload" => ExecutionPayloadBellatrix,
+            "ExecutionPayloadHeader" => ExecutionPayloadHeaderBellatrix,
+            "SignedBeaconBlock" => SignedBeaconBlockBellatrix<P>,
+        }),
+        ForkName::Capella => dispatch!(type_name, case, {
+            "BeaconBlock" => BeaconBlockCapella<P>,
+            "BeaconBlockBody" => BeaconBlockBodyCapella<P>,
+            "BeaconState" => BeaconStateCapella<P>,
+            "ExecutionPayload" => ExecutionPayloadCapella<P>,
+            "ExecutionPayloadHeader" => ExecutionPayloadHeaderCapella,
+            "SignedBeaconBlock" => SignedBeaconBlockCapella<P>,
+        }),
+        ForkName::Deneb => dispatch!(type_name, case, {
+            "BeaconBlock" => BeaconBlock<P>,
+            "BeaconBlockBody" => BeaconBlockBody<P>,
+            "BeaconState" => BeaconStateDeneb<P>,
+            "ExecutionPayload" => ExecutionPayload<P>,
+            "ExecutionPayloadHeader" => ExecutionPayloadHeader,
+            "SignedBeaconBlock" => SignedBeaconBlockDeneb<P>,
+        }),
+        ForkName::Electra => dispatch!(type_name, case, {
+            "BeaconBlock" => BeaconBlockElectra<P>,
+            "BeaconBlockBody" => BeaconBlockBodyElectra<P>,
+            "BeaconState" => BeaconStateElectra<P>,
+            "ExecutionPayload" => ExecutionPayload<P>,
+            "ExecutionPayloadHeader" => ExecutionPayloadHeader,
+            "SignedBeaconBlock" => SignedBeaconBlockElectra<P>,
+        }),
+    }
+}
+
+fn sorted_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+fn run_preset<P: Preset>() {
+    let root = std::env::var("CONSENSUS_SPEC_TESTS_DIR").unwrap_or_else(|_| {
+        panic!(
+            "CONSENSUS_SPEC_TESTS_DIR must point at an extracted consensus-spec-tests release to run {} ssz_static",
+            P::NAME
+        )
+    });
+    let preset_dir = Path::new(&root).join("tests").join(P::NAME);
+    assert!(
+        preset_dir.is_dir(),
+        "{} does not exist, CONSENSUS_SPEC_TESTS_DIR should hold tests/",
+        preset_dir.display()
+    );
+
+    let mut passed = 0;
+    let mut failures = Vec::new();
+    let mut unsupported: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for fork_dir in sorted_dirs(&preset_dir) {
+        let fork_name = file_name(&fork_dir);
+        let Ok(fork) = fork_name.parse::<ForkName>() else {
+            unsupported
+                .entry(fork_name.to_string())
+                .or_default()
+                .insert("<fork>".to_string());
+            continue;
+        };
+        for type_dir in sorted_dirs(&fork_dir.join("ssz_static")) {
+            let type_name = file_name(&type_dir);
+            for suite_dir in sorted_dirs(&type_dir) {
+                for case in sorted_dirs(&suite_dir) {
+                    match run_case::<P>(fork, type_name, &case) {
+                        Some(Ok(())) => passed += 1,
+                        Some(Err(e)) => failures.push(format!(
+                            "{fork}/{type_name}/{}/{}: {e}",
+                            file_name(&suite_dir),
+                            file_name(&case)
+                        )),
+                        None => {
+                            unsupported
+                                .entry(fork.to_string())
+                                .or_default()
+                                .insert(type_name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    eprintln!("{} ssz_static: {passed} cases passed", P::NAME);
+    for (fork, types) in &unsupported {
+        let types: Vec<&str> = types.iter().map(String::as_str).collect();
+        eprintln!("unsupported in {fork}: {}", types.join(", "));
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} {} ssz_static cases failed:\n{}",
+        failures.len(),
+        failures.len() + passed,
+        P::NAME,
+        failures.join("\n")
+    );
+}
+
+#[test]
+#[ignore = "needs CONSENSUS_SPEC_TESTS_DIR pointing at consensus-spec-tests"]
+fn ssz_static_mainnet() {
+    run_preset::<MainnetPreset>();
+}
+
+#[test]
+#[ignore = "needs CONSENSUS_SPEC_TESTS_DIR pointing at consensus-spec-tests"]
+fn ssz_static_minimal() {
+    run_preset::<MinimalPreset>();
+}